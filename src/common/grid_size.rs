@@ -1 +1,13 @@
 pub const GRID_SIZE: usize = 9;
+
+pub const fn get_box_size(grid_size: usize) -> (usize, usize) {
+    let mut rows = 1;
+    let mut candidate = 2;
+    while candidate * candidate <= grid_size {
+        if grid_size.is_multiple_of(candidate) {
+            rows = candidate;
+        }
+        candidate += 1;
+    }
+    (rows, grid_size / rows)
+}
@@ -1,3 +1,3 @@
 use crate::common::grid_size::GRID_SIZE;
 
-pub type Puzzle = [[u8; GRID_SIZE]; GRID_SIZE];
+pub type Puzzle<const N: usize = GRID_SIZE> = [[u8; N]; N];
@@ -1,6 +1,7 @@
 use fltk::prelude::WidgetExt;
 use crate::gui::board::Board;
 use crate::common::puzzle::Puzzle;
+use crate::solve::notes::Notes;
 
 pub fn read_puzzle(board: &Board) -> Puzzle {
     let mut output: Puzzle = Default::default();
@@ -14,3 +15,20 @@ pub fn read_puzzle(board: &Board) -> Puzzle {
     }
     output
 }
+
+pub fn read_notes(board: &Board, computed: Notes) -> Notes {
+    let mut output = computed;
+    for (row, buttons) in board.iter().enumerate() {
+        for (col, button) in buttons.iter().enumerate() {
+            let label = button.label();
+            if label.len() > 1 {
+                output[row][col] = label
+                    .chars()
+                    .filter_map(|character| character.to_digit(10))
+                    .map(|value| value as usize)
+                    .collect();
+            }
+        }
+    }
+    output
+}
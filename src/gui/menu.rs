@@ -3,12 +3,19 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use crate::gui::play_board::PlayBoard;
 use crate::gui::consts::MENU_WIDTH;
+use crate::solve::technique::Pipeline;
 
 const SAVE: &str = "Save";
 const READ: &str = "Read";
 const CLEAR: &str = "Clear";
 const SOLVE: &str = "Solve";
+const SOLVE_WITHOUT_UNIQUENESS: &str = "Solve without uniqueness";
+const EXPLAIN: &str = "Solve step by step";
+const BEGINNER: &str = "Solve with beginner techniques";
+const BRUTE_FORCE: &str = "Brute force";
 const SHOW_NOTES: &str = "Show notes";
+const HINT: &str = "Hint";
+const RATE: &str = "Rate difficulty";
 
 const MENU_LENGTH: i32 = 60;
 
@@ -35,7 +42,13 @@ impl Menu {
         self.file_menu.add_choice(CLEAR);
         self.set_file_callback();
         self.solve_menu.add_choice(SOLVE);
+        self.solve_menu.add_choice(SOLVE_WITHOUT_UNIQUENESS);
+        self.solve_menu.add_choice(EXPLAIN);
+        self.solve_menu.add_choice(BEGINNER);
+        self.solve_menu.add_choice(BRUTE_FORCE);
         self.solve_menu.add_choice(SHOW_NOTES);
+        self.solve_menu.add_choice(HINT);
+        self.solve_menu.add_choice(RATE);
         self.set_solve_callback();
     }
 
@@ -73,11 +86,29 @@ impl Menu {
             if let Some(choice) = solve_menu_clone.choice() {
                 match choice.as_str() {
                     SOLVE => {
-                        board_clone.borrow_mut().solve_puzzle();
+                        board_clone.borrow_mut().solve_puzzle(true, false, Pipeline::default());
+                    }
+                    SOLVE_WITHOUT_UNIQUENESS => {
+                        board_clone.borrow_mut().solve_puzzle(false, false, Pipeline::default());
+                    }
+                    EXPLAIN => {
+                        board_clone.borrow_mut().solve_puzzle(true, true, Pipeline::default());
+                    }
+                    BEGINNER => {
+                        board_clone.borrow_mut().solve_puzzle(true, false, Pipeline::beginner());
+                    }
+                    BRUTE_FORCE => {
+                        board_clone.borrow_mut().brute_force();
                     }
                     SHOW_NOTES => {
                         board_clone.borrow_mut().show_notes();
                     }
+                    HINT => {
+                        board_clone.borrow_mut().show_hint();
+                    }
+                    RATE => {
+                        board_clone.borrow().rate_puzzle();
+                    }
                     _ => {}
                 }
             }
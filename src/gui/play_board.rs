@@ -1,18 +1,32 @@
-use fltk::{ prelude::*, button::Button, group::Pack };
+use fltk::{ prelude::*, button::Button, group::Pack, draw };
+use fltk::enums::{ Color, Font };
 use std::cell::RefCell;
 use std::rc::Rc;
 use crate::common::grid_size::GRID_SIZE;
 use crate::common::puzzle::Puzzle;
-use crate::gui::adapter::read_puzzle;
+use crate::gui::adapter::*;
 use crate::gui::board::Board;
 use crate::gui::button::*;
 use crate::gui::consts::*;
 use crate::gui::save_handler::*;
+use crate::solve::cages::Cage;
+use crate::solve::coordinates::{ Layout, Point, Variant };
+use crate::solve::dancing_links;
+use crate::solve::hint::hint;
+use crate::solve::lines::{ Line, LineKind };
+use crate::solve::notes_manager::NotesManager;
+use crate::solve::candidate_set::CandidateSet;
+use crate::solve::rating::rate;
+use crate::solve::solution_count::*;
 use crate::solve::solver::Solver;
+use crate::solve::technique::Pipeline;
 
 pub struct PlayBoard {
     play_grid: Rc<RefCell<Board>>,
     current_number: Rc<RefCell<String>>,
+    cages: Rc<RefCell<Vec<Cage>>>,
+    lines: Rc<RefCell<Vec<Line>>>,
+    variants: Vec<Variant>,
 }
 
 impl PlayBoard {
@@ -20,6 +34,9 @@ impl PlayBoard {
         PlayBoard {
             play_grid: Default::default(),
             current_number: Rc::new(RefCell::new("1".to_string())),
+            cages: Default::default(),
+            lines: Default::default(),
+            variants: Vec::new(),
         }
     }
 
@@ -47,6 +64,91 @@ impl PlayBoard {
         *button = Self::create_button(row, col);
         Self::format_label(button);
         self.set_callback(button);
+        let cages = Rc::clone(&self.cages);
+        let lines = Rc::clone(&self.lines);
+        button.draw(move |button| {
+            Self::draw_cage((row, col), button, &cages.borrow());
+            Self::draw_lines((row, col), button, &lines.borrow());
+        });
+    }
+
+    fn draw_cage((row, col): Point, button: &Button, cages: &[Cage]) {
+        let Some(cage) = cages.iter().find(|cage| cage.cells.contains(&(row, col))) else {
+            return;
+        };
+        let is_border = |neighbour: Option<Point>| {
+            neighbour.is_none_or(|cell| !cage.cells.contains(&cell))
+        };
+        let inset = 3;
+        let (left, top) = (button.x() + inset, button.y() + inset);
+        let (right, bottom) = (button.x() + button.w() - inset, button.y() + button.h() - inset);
+        draw::set_draw_color(Color::from_rgb(80, 80, 80));
+        draw::set_line_style(draw::LineStyle::Dash, 1);
+        if is_border(row.checked_sub(1).map(|above| (above, col))) {
+            draw::draw_line(left, top, right, top);
+        }
+        if is_border(Some((row + 1, col))) {
+            draw::draw_line(left, bottom, right, bottom);
+        }
+        if is_border(col.checked_sub(1).map(|before| (row, before))) {
+            draw::draw_line(left, top, left, bottom);
+        }
+        if is_border(Some((row, col + 1))) {
+            draw::draw_line(right, top, right, bottom);
+        }
+        draw::set_line_style(draw::LineStyle::Solid, 0);
+        if cage.cells.iter().min() == Some(&(row, col)) {
+            draw::set_font(Font::Helvetica, 10);
+            draw::draw_text(&cage.sum.to_string(), left + 2, top + 10);
+        }
+    }
+
+    fn draw_lines((row, col): Point, button: &Button, lines: &[Line]) {
+        let (center_x, center_y) = (button.x() + button.w() / 2, button.y() + button.h() / 2);
+        let offset = |from: usize, to: usize, size: i32| (to as i32 - from as i32) * size / 2;
+        let towards = |(other_row, other_col): Point| {
+            let x = center_x + offset(col, other_col, button.w());
+            let y = center_y + offset(row, other_row, button.h());
+            (x, y)
+        };
+        for line in lines {
+            let Some(index) = line.cells.iter().position(|cell| *cell == (row, col)) else {
+                continue;
+            };
+            let color = match line.kind {
+                LineKind::Thermometer => Color::from_rgb(160, 160, 160),
+                LineKind::Arrow => Color::from_rgb(120, 120, 120),
+                LineKind::Palindrome => Color::from_rgb(140, 140, 220),
+                LineKind::Whispers => Color::from_rgb(100, 200, 100),
+            };
+            draw::set_draw_color(color);
+            draw::set_line_style(draw::LineStyle::Solid, 3);
+            let neighbours = [index.checked_sub(1), Some(index + 1)];
+            for cell in neighbours.into_iter().flatten().filter_map(|other| line.cells.get(other)) {
+                let (x, y) = towards(*cell);
+                draw::draw_line(center_x, center_y, x, y);
+            }
+            let is_bulb = matches!(line.kind, LineKind::Thermometer | LineKind::Arrow);
+            if is_bulb && index == 0 {
+                let radius = (button.w() / 3) as f64;
+                draw::draw_circle(center_x as f64, center_y as f64, radius);
+            }
+            draw::set_line_style(draw::LineStyle::Solid, 0);
+        }
+    }
+
+    fn get_layout(&self) -> Option<Layout> {
+        let layout = Layout::default()
+            .with_cages(self.cages.borrow().clone())
+            .and_then(|layout| layout.with_lines(self.lines.borrow().clone()));
+        let layout = match layout {
+            Ok(layout) => layout,
+            Err(err) => {
+                fltk::dialog::alert_default(&err.to_string());
+                return None;
+            }
+        };
+        Some(self.variants.iter().fold(layout, |layout, variant| layout.with_variant(*variant)))
     }
 
     fn format_label(button: &mut Button) {
@@ -85,6 +187,9 @@ impl PlayBoard {
     }
 
     pub fn clear(&mut self) {
+        self.cages.borrow_mut().clear();
+        self.lines.borrow_mut().clear();
+        self.variants.clear();
         for play_row in self.play_grid.borrow_mut().iter_mut() {
             for button in play_row.iter_mut() {
                 button.set_label("");
@@ -94,13 +199,22 @@ impl PlayBoard {
     }
 
     pub fn to_json(&self) -> Result<(), Box<dyn std::error::Error>> {
-        to_json("boards/board.json", &self.play_grid.borrow())
+        to_json(
+            "boards/board.json",
+            &self.play_grid.borrow(),
+            &self.cages.borrow(),
+            &self.lines.borrow(),
+            &self.variants
+        )
     }
 
-    pub fn read_from_file(&self) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn read_from_file(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         match from_json("boards/board.json") {
-            Ok(labels) => {
-                self.display_content_from_labels(&labels);
+            Ok(saved) => {
+                *self.cages.borrow_mut() = saved.cages;
+                *self.lines.borrow_mut() = saved.lines;
+                self.variants = saved.variants;
+                self.display_content_from_labels(&saved.labels);
                 Ok(())
             }
             Err(e) => Err(e),
@@ -121,27 +235,147 @@ impl PlayBoard {
         }
     }
 
-    pub fn solve_puzzle(&mut self) {
-        let mut solver = Solver::new(read_puzzle(&self.play_grid.borrow()));
-        solver.solve();
+    pub fn solve_puzzle(&mut self, use_uniqueness: bool, explain: bool, pipeline: Pipeline) {
+        let puzzle = read_puzzle(&self.play_grid.borrow());
+        let Some(layout) = self.get_layout() else {
+            return;
+        };
+        let mut solver = match Solver::with_layout(layout.clone(), puzzle) {
+            Ok(solver) => solver,
+            Err(err) => {
+                fltk::dialog::alert_default(&err.to_string());
+                return;
+            }
+        };
+        let solution_count = match count_solutions(&layout, &puzzle, DEFAULT_CAP) {
+            Ok(solution_count) => solution_count,
+            Err(err) => {
+                fltk::dialog::alert_default(&err.to_string());
+                return;
+            }
+        };
+        if solution_count == SolutionCount::NoSolution {
+            fltk::dialog::alert_default("The puzzle has no solution");
+            return;
+        }
+        solver.set_uniqueness(use_uniqueness && solution_count.is_unique());
+        solver.set_tracing(explain);
+        let difficulty = pipeline.get_difficulty();
+        let techniques = format!("{} (difficulty up to {:.1})", pipeline, difficulty);
+        solver.set_pipeline(pipeline);
+        if let Err(err) = solver.solve() {
+            fltk::dialog::alert_default(&err.to_string());
+            return;
+        }
         self.display_puzzle(&solver.get_solution());
+        if let SolutionCount::Multiple { solutions, .. } = solution_count {
+            self.highlight_differences(&solutions);
+            fltk::dialog::alert_default("The puzzle has more than one solution");
+        } else if solver.needed_guessing() {
+            fltk::dialog::message_default(&format!(
+                "Logic techniques were not enough, guessing was needed\nTechniques: {}",
+                techniques
+            ));
+        }
+        if explain {
+            Self::show_explanation(&solver);
+        }
+    }
+
+    fn show_explanation(solver: &Solver) {
+        let steps: Vec<String> = solver
+            .get_trace()
+            .enumerate()
+            .map(|(index, step)| format!("<p>{}. {}</p>", index + 1, step))
+            .collect();
+        let mut dialog = fltk::dialog::HelpDialog::default();
+        dialog.set_value(&steps.concat());
+        dialog.show();
+    }
+
+    fn highlight_differences(&mut self, [first, second]: &[Puzzle; 2]) {
+        for (row, buttons) in self.play_grid.borrow_mut().iter_mut().enumerate() {
+            for (col, button) in buttons.iter_mut().enumerate() {
+                if first[row][col] != second[row][col] {
+                    highlight_on(button);
+                }
+            }
+        }
+    }
+
+    pub fn brute_force(&mut self) {
+        let Some(layout) = self.get_layout() else {
+            return;
+        };
+        match dancing_links::solve(&layout, &read_puzzle(&self.play_grid.borrow())) {
+            Some(solution) => self.display_puzzle(&solution),
+            None => fltk::dialog::alert_default("The puzzle has no solution"),
+        }
+    }
+
+    pub fn show_hint(&mut self) {
+        let puzzle = read_puzzle(&self.play_grid.borrow());
+        let Some(layout) = self.get_layout() else {
+            return;
+        };
+        let mut notes_manager = NotesManager::with_layout(layout, puzzle);
+        if let Err(err) = notes_manager.fill() {
+            fltk::dialog::alert_default(&err.to_string());
+            return;
+        }
+        let notes = read_notes(&self.play_grid.borrow(), notes_manager.get());
+        let layout = notes_manager.get_layout();
+        let uniqueness = match count_solutions(layout, &puzzle, DEFAULT_CAP) {
+            Ok(solution_count) => solution_count.is_unique(),
+            Err(err) => {
+                fltk::dialog::alert_default(&err.to_string());
+                return;
+            }
+        };
+        match hint(layout, &puzzle, &notes, uniqueness) {
+            Ok(Some(deduction)) => {
+                self.clear_highlight();
+                for (row, col) in deduction.reasons.iter() {
+                    highlight_on(&mut self.play_grid.borrow_mut()[*row][*col]);
+                }
+                fltk::dialog::message_default(&deduction.to_string());
+            }
+            Ok(None) => fltk::dialog::message_default("No hint available"),
+            Err(err) => fltk::dialog::alert_default(&err.to_string()),
+        }
+    }
+
+    pub fn rate_puzzle(&self) {
+        let Some(layout) = self.get_layout() else {
+            return;
+        };
+        match rate(&layout, &read_puzzle(&self.play_grid.borrow())) {
+            Ok(rating) => fltk::dialog::message_default(&rating.to_string()),
+            Err(err) => fltk::dialog::alert_default(&err.to_string()),
+        }
     }
 
     pub fn show_notes(&mut self) {
-        use crate::solve::notes_manager::NotesManager;
-        let mut notes_manager = NotesManager::new(read_puzzle(&self.play_grid.borrow()));
-        notes_manager.fill();
+        let puzzle = read_puzzle(&self.play_grid.borrow());
+        let Some(layout) = self.get_layout() else {
+            return;
+        };
+        let mut notes_manager = NotesManager::with_layout(layout, puzzle);
+        if let Err(err) = notes_manager.fill() {
+            fltk::dialog::alert_default(&err.to_string());
+            return;
+        }
         let notes = notes_manager.get();
         for (row, x) in notes.iter().enumerate() {
             for (col, note) in x.iter().enumerate() {
-                if notes[row][col] != 0 {
+                if !notes[row][col].is_empty() {
                     Self::display_note(&mut self.play_grid.borrow_mut()[row][col], *note);
                 }
             }
         }
     }
 
-    fn display_note(button: &mut Button, note: u16) {
+    fn display_note(button: &mut Button, note: CandidateSet) {
         Self::format_note(button, &Self::note_to_string(note).to_string());
     }
 
@@ -152,10 +386,10 @@ impl PlayBoard {
         button.redraw();
     }
 
-    fn note_to_string(note: u16) -> String {
+    fn note_to_string(note: CandidateSet) -> String {
         let mut output = String::new();
         for i in 0..GRID_SIZE {
-            if (note & (1 << i)) != 0 {
+            if note.contains(i + 1) {
                 output.push_str(&(i + 1).to_string());
             } else {
                 output.push(' ');
@@ -171,7 +405,8 @@ impl PlayBoard {
         for (row, sol_row) in solution.iter().enumerate() {
             for (col, cell) in sol_row.iter().enumerate() {
                 let button = &mut self.play_grid.borrow_mut()[row][col];
-                button.set_label(&cell.to_string());
+                let label = if *cell != 0 { cell.to_string() } else { String::new() };
+                button.set_label(&label);
                 Self::format_label(button);
                 highlight_off(button);
             }
@@ -2,16 +2,46 @@ use serde::Serialize;
 use serde::Deserialize;
 use crate::gui::board::*;
 use crate::gui::translator::*;
+use crate::solve::cages::Cage;
+use crate::solve::lines::Line;
+use crate::solve::coordinates::Variant;
 
 #[derive(Serialize, Deserialize)]
 pub struct Saver;
 
-pub fn to_json(file_path: &str, board: &Board) -> Result<(), Box<dyn std::error::Error>> {
-    let json_content = serde_json::to_string_pretty(&labels(board))?;
+#[derive(Serialize, Deserialize)]
+pub struct SavedBoard {
+    pub labels: Labels,
+    #[serde(default)]
+    pub cages: Vec<Cage>,
+    #[serde(default)]
+    pub lines: Vec<Line>,
+    #[serde(default)]
+    pub variants: Vec<Variant>,
+}
+
+pub fn to_json(
+    file_path: &str,
+    board: &Board,
+    cages: &[Cage],
+    lines: &[Line],
+    variants: &[Variant]
+) -> Result<(), Box<dyn std::error::Error>> {
+    let saved = SavedBoard {
+        labels: labels(board),
+        cages: cages.to_vec(),
+        lines: lines.to_vec(),
+        variants: variants.to_vec(),
+    };
+    let json_content = serde_json::to_string_pretty(&saved)?;
     Ok(std::fs::write(file_path, json_content)?)
 }
 
-pub fn from_json(file_path: &str) -> Result<Labels, Box<dyn std::error::Error>> {
+pub fn from_json(file_path: &str) -> Result<SavedBoard, Box<dyn std::error::Error>> {
     let json_content = std::fs::read_to_string(file_path)?;
-    Ok(serde_json::from_str(&json_content)?)
+    if let Ok(saved) = serde_json::from_str(&json_content) {
+        return Ok(saved);
+    }
+    let labels = serde_json::from_str(&json_content)?; // boards saved before cages were added
+    Ok(SavedBoard { labels, cages: Vec::new(), lines: Vec::new(), variants: Vec::new() })
 }
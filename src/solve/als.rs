@@ -0,0 +1,398 @@
+use crate::solve::notes::*;
+use crate::solve::coordinates::*;
+use crate::solve::chains::Candidate;
+use crate::solve::deduction::Deduction;
+use crate::solve::fish::combinations;
+use crate::solve::candidate_set::CandidateSet;
+
+const MAX_SUE_DE_COQ_SET: usize = 3;
+const MAX_ALS_SIZE: usize = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Als {
+    pub cells: Vec<Point>,
+    pub note: CandidateSet,
+}
+
+impl Als {
+    fn cells_with<const N: usize>(&self, notes: &Notes<N>, value: usize) -> Vec<Point> {
+        self.cells
+            .iter()
+            .filter(|(row, col)| notes[*row][*col].contains(value))
+            .copied()
+            .collect()
+    }
+
+    fn overlaps(&self, other: &Als) -> bool {
+        self.cells.iter().any(|cell| other.cells.contains(cell))
+    }
+
+    fn get_restricted_commons<const N: usize>(
+        &self,
+        layout: &Layout<N>,
+        notes: &Notes<N>,
+        other: &Als
+    ) -> Vec<usize> {
+        (self.note & other.note)
+            .iter()
+            .filter(|value| {
+                let other_cells = other.cells_with(notes, *value);
+                self.cells_with(notes, *value).iter().all(|cell| {
+                    other_cells.iter().all(|other_cell| layout.sees(*cell, *other_cell))
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlsKind {
+    AlsXz,
+    AlsXyWing,
+    SueDeCoq,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AlsPattern {
+    pub kind: AlsKind,
+    pub sets: Vec<Vec<Point>>,
+    pub eliminations: Vec<Candidate>,
+}
+
+impl AlsKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlsKind::AlsXz => "ALS-XZ",
+            AlsKind::AlsXyWing => "ALS-XY-Wing",
+            AlsKind::SueDeCoq => "Sue de Coq",
+        }
+    }
+}
+
+impl AlsPattern {
+    pub fn clear<const N: usize>(&self, notes: &mut Notes<N>) -> bool {
+        let mut result = false;
+        for (cell, value) in self.eliminations.iter() {
+            result |= clear_value(notes, *value, &[*cell]);
+        }
+        result
+    }
+
+    pub fn apply<const N: usize>(&self, notes: &mut Notes<N>) -> Option<Deduction> {
+        let reasons: Vec<Point> = self.sets.iter().flatten().copied().collect();
+        Deduction::record(self.kind.name(), &reasons, notes, |notes| self.clear(notes))
+    }
+}
+
+pub fn use_als<const N: usize>(layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+    for finder in [find_als_xz, find_als_xy_wings, find_sue_de_coq] {
+        let deductions: Vec<Deduction> = finder(layout, notes)
+            .iter()
+            .filter_map(|pattern| pattern.apply(notes))
+            .collect();
+        if !deductions.is_empty() {
+            return deductions;
+        }
+    }
+    Vec::new()
+}
+
+pub fn get_almost_locked_sets<const N: usize>(layout: &Layout<N>, notes: &Notes<N>) -> Vec<Als> {
+    let mut found: Vec<Als> = Vec::new();
+    layout.perform_for_all_sets(|coordinates| {
+        let unsolved: Vec<usize> = (0..N)
+            .filter(|index| !notes[coordinates[*index].0][coordinates[*index].1].is_empty())
+            .collect();
+        for size in 1..unsolved.len().min(MAX_ALS_SIZE + 1) {
+            for subset in combinations(&unsolved, size) {
+                let mut cells: Vec<Point> = subset
+                    .iter()
+                    .map(|index| coordinates[*index])
+                    .collect();
+                let note = get_note(notes, &cells);
+                cells.sort();
+                let als = Als { cells, note };
+                if note.count() == size + 1 && !found.contains(&als) {
+                    found.push(als);
+                }
+            }
+        }
+        false
+    });
+    found
+}
+
+pub fn find_als_xz<const N: usize>(layout: &Layout<N>, notes: &Notes<N>) -> Vec<AlsPattern> {
+    let sets = get_almost_locked_sets(layout, notes);
+    let mut found = Vec::new();
+    for (index, first) in sets.iter().enumerate() {
+        for second in sets[index + 1..].iter() {
+            if first.overlaps(second) {
+                continue;
+            }
+            let restricted = first.get_restricted_commons(layout, notes, second);
+            let eliminations = match restricted.len() {
+                0 => {
+                    continue;
+                }
+                1 => get_common_eliminations(layout, notes, &[first, second], &restricted),
+                _ => get_doubly_linked_eliminations(layout, notes, first, second, &restricted),
+            };
+            if !eliminations.is_empty() {
+                found.push(AlsPattern {
+                    kind: AlsKind::AlsXz,
+                    sets: vec![first.cells.clone(), second.cells.clone()],
+                    eliminations,
+                });
+            }
+        }
+    }
+    found
+}
+
+fn get_common_eliminations<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    sets: &[&Als; 2],
+    excluded: &[usize]
+) -> Vec<Candidate> {
+    let mut eliminations = Vec::new();
+    for value in (sets[0].note & sets[1].note).iter() {
+        if !excluded.contains(&value) {
+            let mut cells = sets[0].cells_with(notes, value);
+            cells.extend(sets[1].cells_with(notes, value));
+            eliminations.extend(get_seeing_all(layout, notes, value, &cells, sets));
+        }
+    }
+    eliminations
+}
+
+fn get_doubly_linked_eliminations<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    first: &Als,
+    second: &Als,
+    restricted: &[usize]
+) -> Vec<Candidate> {
+    let mut eliminations = Vec::new();
+    for value in restricted.iter() {
+        let mut cells = first.cells_with(notes, *value);
+        cells.extend(second.cells_with(notes, *value));
+        eliminations.extend(get_seeing_all(layout, notes, *value, &cells, &[first, second]));
+    }
+    for als in [first, second] {
+        for value in als.note.iter() {
+            if !restricted.contains(&value) {
+                let cells = als.cells_with(notes, value);
+                eliminations.extend(get_seeing_all(layout, notes, value, &cells, &[als]));
+            }
+        }
+    }
+    eliminations
+}
+
+pub fn find_als_xy_wings<const N: usize>(layout: &Layout<N>, notes: &Notes<N>) -> Vec<AlsPattern> {
+    let sets = get_almost_locked_sets(layout, notes);
+    let mut links: Vec<Vec<(usize, Vec<usize>)>> = vec![Vec::new(); sets.len()];
+    for (index, first) in sets.iter().enumerate() {
+        for (other, second) in sets.iter().enumerate().skip(index + 1) {
+            if first.overlaps(second) {
+                continue;
+            }
+            let restricted = first.get_restricted_commons(layout, notes, second);
+            if !restricted.is_empty() {
+                links[index].push((other, restricted.clone()));
+                links[other].push((index, restricted));
+            }
+        }
+    }
+    let mut found = Vec::new();
+    for (pivot, pivot_links) in links.iter().enumerate() {
+        for (index, (first, first_restricted)) in pivot_links.iter().enumerate() {
+            for (second, second_restricted) in pivot_links[index + 1..].iter() {
+                let (first_set, second_set) = (&sets[*first], &sets[*second]);
+                if first_set.overlaps(second_set) {
+                    continue;
+                }
+                for x in first_restricted.iter() {
+                    for y in second_restricted.iter().filter(|y| *y != x) {
+                        let eliminations = get_common_eliminations(
+                            layout,
+                            notes,
+                            &[first_set, second_set],
+                            &[*x, *y]
+                        );
+                        if !eliminations.is_empty() {
+                            found.push(AlsPattern {
+                                kind: AlsKind::AlsXyWing,
+                                sets: vec![
+                                    first_set.cells.clone(),
+                                    sets[pivot].cells.clone(),
+                                    second_set.cells.clone()
+                                ],
+                                eliminations,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+    found
+}
+
+pub fn find_sue_de_coq<const N: usize>(layout: &Layout<N>, notes: &Notes<N>) -> Vec<AlsPattern> {
+    let mut found = Vec::new();
+    for region in 0..N {
+        let box_cells = layout.get_region_coordinates(region);
+        for line in get_crossing_lines(layout, &box_cells) {
+            let intersection: Vec<Point> = box_cells
+                .iter()
+                .filter(|cell| line.contains(cell) && !notes[cell.0][cell.1].is_empty())
+                .copied()
+                .collect();
+            for size in 2..=intersection.len() {
+                for subset in combinations(&(0..intersection.len()).collect::<Vec<_>>(), size) {
+                    let core: Vec<Point> = subset
+                        .iter()
+                        .map(|index| intersection[*index])
+                        .collect();
+                    found.extend(check_sue_de_coq(notes, &core, &line, &box_cells));
+                }
+            }
+        }
+    }
+    found
+}
+
+fn get_crossing_lines<const N: usize>(
+    layout: &Layout<N>,
+    region_cells: &Coordinates<N>
+) -> Vec<Coordinates<N>> {
+    let mut rows: Vec<usize> = region_cells.iter().map(|(row, _)| *row).collect();
+    let mut cols: Vec<usize> = region_cells.iter().map(|(_, col)| *col).collect();
+    rows.dedup();
+    cols.sort();
+    cols.dedup();
+    let mut lines = Vec::new();
+    for offset in 0..rows.len().max(cols.len()) {
+        if let Some(row) = rows.get(offset) {
+            lines.push(layout.get_row_coordinates(*row));
+        }
+        if let Some(col) = cols.get(offset) {
+            lines.push(layout.get_col_coordinates(*col));
+        }
+    }
+    lines
+}
+
+fn check_sue_de_coq<const N: usize>(
+    notes: &Notes<N>,
+    core: &[Point],
+    line: &Coordinates<N>,
+    box_cells: &Coordinates<N>
+) -> Vec<AlsPattern> {
+    let core_note = get_note(notes, core);
+    if core_note.count() < core.len() + 2 {
+        return Vec::new();
+    }
+    let line_rest = get_unsolved(notes, line, box_cells);
+    let box_rest = get_unsolved(notes, box_cells, line);
+    let mut found = Vec::new();
+    for line_set in get_subsets(notes, &line_rest, core_note) {
+        for box_set in get_subsets(notes, &box_rest, core_note) {
+            let (line_note, box_note) = (get_note(notes, &line_set), get_note(notes, &box_set));
+            let note = core_note | line_note | box_note;
+            let size = core.len() + line_set.len() + box_set.len();
+            if !(line_note & box_note).is_empty() || note.count() != size {
+                continue;
+            }
+            let mut used = core.to_vec();
+            used.extend(line_set.iter().chain(box_set.iter()));
+            let mut eliminations = get_house_eliminations(notes, line, note - box_note, &used);
+            eliminations.extend(get_house_eliminations(notes, box_cells, note - line_note, &used));
+            if !eliminations.is_empty() {
+                found.push(AlsPattern {
+                    kind: AlsKind::SueDeCoq,
+                    sets: vec![core.to_vec(), line_set.clone(), box_set],
+                    eliminations,
+                });
+            }
+        }
+    }
+    found
+}
+
+fn get_unsolved<const N: usize>(
+    notes: &Notes<N>,
+    house: &Coordinates<N>,
+    excluded: &Coordinates<N>
+) -> Vec<Point> {
+    house
+        .iter()
+        .filter(|(row, col)| !notes[*row][*col].is_empty() && !excluded.contains(&(*row, *col)))
+        .copied()
+        .collect()
+}
+
+fn get_subsets<const N: usize>(
+    notes: &Notes<N>,
+    cells: &[Point],
+    core_note: CandidateSet
+) -> Vec<Vec<Point>> {
+    let mut subsets = Vec::new();
+    let indexes: Vec<usize> = (0..cells.len()).collect();
+    for size in 1..=MAX_SUE_DE_COQ_SET.min(cells.len()) {
+        for subset in combinations(&indexes, size) {
+            let subset: Vec<Point> = subset.iter().map(|index| cells[*index]).collect();
+            if !(get_note(notes, &subset) & core_note).is_empty() {
+                subsets.push(subset);
+            }
+        }
+    }
+    subsets
+}
+
+fn get_house_eliminations<const N: usize>(
+    notes: &Notes<N>,
+    house: &Coordinates<N>,
+    note: CandidateSet,
+    used: &[Point]
+) -> Vec<Candidate> {
+    let mut eliminations = Vec::new();
+    for (row, col) in house.iter().filter(|cell| !used.contains(cell)) {
+        for value in (notes[*row][*col] & note).iter() {
+            eliminations.push(((*row, *col), value));
+        }
+    }
+    eliminations
+}
+
+fn get_seeing_all<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize,
+    cells: &[Point],
+    sets: &[&Als]
+) -> Vec<Candidate> {
+    let mut eliminations = Vec::new();
+    for (row, notes_row) in notes.iter().enumerate() {
+        for (col, note) in notes_row.iter().enumerate() {
+            let is_outside = sets.iter().all(|als| !als.cells.contains(&(row, col)));
+            let sees_all = cells.iter().all(|cell| layout.sees(*cell, (row, col)));
+            if note.contains(value) && is_outside && sees_all {
+                eliminations.push(((row, col), value));
+            }
+        }
+    }
+    eliminations
+}
+
+fn get_note<const N: usize>(notes: &Notes<N>, cells: &[Point]) -> CandidateSet {
+    cells.iter().fold(CandidateSet::EMPTY, |note, (row, col)| note | notes[*row][*col])
+}
+
+#[cfg(test)]
+mod tests {
+    include!("ut/test_als.rs");
+}
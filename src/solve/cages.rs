@@ -0,0 +1,284 @@
+use serde::Serialize;
+use serde::Deserialize;
+use crate::common::puzzle::Puzzle;
+use crate::solve::notes::Notes;
+use crate::solve::coordinates::*;
+use crate::solve::candidate_set::CandidateSet;
+use crate::solve::deduction::Deduction;
+use crate::solve::fish::combinations;
+use crate::solve::hidden_sets::NAKED_SETS;
+
+pub const CAGE_COMBINATION: &str = "Cage Combination";
+pub const INNIES_AND_OUTIES: &str = "Innies and Outies";
+const MAX_VIRTUAL_CAGE: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cage {
+    pub cells: Vec<Point>,
+    pub sum: usize,
+}
+
+impl Cage {
+    pub fn fits<const N: usize>(&self, puzzle: &Puzzle<N>, notes: &Notes<N>) -> bool {
+        let mut used = CandidateSet::EMPTY;
+        let mut total = 0;
+        let mut empty = Vec::new();
+        for (row, col) in self.cells.iter().copied() {
+            let value = puzzle[row][col] as usize;
+            if value == 0 {
+                empty.push((row, col));
+            } else if used.contains(value) {
+                return false;
+            } else {
+                used.insert(value);
+                total += value;
+            }
+        }
+        total <= self.sum && can_reach(notes, &empty, self.sum - total, used)
+    }
+
+    pub fn without_solved<const N: usize>(&self, puzzle: &Puzzle<N>) -> Cage {
+        let placed: usize = self.cells.iter().map(|(row, col)| puzzle[*row][*col] as usize).sum();
+        let cells = self.cells
+            .iter()
+            .copied()
+            .filter(|(row, col)| puzzle[*row][*col] == 0)
+            .collect();
+        Cage { cells, sum: self.sum.saturating_sub(placed) }
+    }
+}
+
+fn can_reach<const N: usize>(
+    notes: &Notes<N>,
+    cells: &[Point],
+    sum: usize,
+    used: CandidateSet
+) -> bool {
+    let Some(((row, col), rest)) = cells.split_first() else {
+        return sum == 0;
+    };
+    let values = cells
+        .iter()
+        .fold(CandidateSet::EMPTY, |union, (row, col)| union | notes[*row][*col]);
+    let is_in_range = get_sum_range(values - used, cells.len())
+        .is_some_and(|(low, high)| low <= sum && sum <= high);
+    is_in_range && (notes[*row][*col] - used)
+        .iter()
+        .take_while(|value| *value <= sum)
+        .any(|value| can_reach(notes, rest, sum - value, used | CandidateSet::from_value(value)))
+}
+
+fn get_sum_range(values: CandidateSet, count: usize) -> Option<(usize, usize)> {
+    if values.count() < count {
+        return None;
+    }
+    let low = values.iter().take(count).sum();
+    let high = values.iter().skip(values.count() - count).sum();
+    Some((low, high))
+}
+
+pub fn use_cages<const N: usize>(layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+    for finder in [use_cage_combinations, use_innies_and_outies, use_cage_naked_sets] {
+        let deductions = finder(layout, notes);
+        if !deductions.is_empty() {
+            return deductions;
+        }
+    }
+    Vec::new()
+}
+
+pub fn use_cage_combinations<const N: usize>(
+    layout: &Layout<N>,
+    notes: &mut Notes<N>
+) -> Vec<Deduction> {
+    let mut deductions = Vec::new();
+    for cage in layout.get_cages() {
+        deductions.extend(restrict_to_sum(CAGE_COMBINATION, layout, notes, &cage.cells, cage.sum));
+    }
+    deductions
+}
+
+pub fn use_innies_and_outies<const N: usize>(
+    layout: &Layout<N>,
+    notes: &mut Notes<N>
+) -> Vec<Deduction> {
+    let mut deductions = Vec::new();
+    layout.perform_for_all_sets(|house| {
+        let innies = get_innies(layout, notes, house);
+        let outies = get_outies(layout, notes, house);
+        for (cells, sum) in innies.into_iter().chain(outies) {
+            deductions.extend(restrict_to_sum(INNIES_AND_OUTIES, layout, notes, &cells, sum));
+        }
+        false
+    });
+    deductions
+}
+
+pub fn use_cage_naked_sets<const N: usize>(
+    layout: &Layout<N>,
+    notes: &mut Notes<N>
+) -> Vec<Deduction> {
+    let mut deductions = Vec::new();
+    for cage in layout.get_cages() {
+        let indexes: Vec<usize> = (0..cage.cells.len()).collect();
+        for (technique, size) in NAKED_SETS.iter().zip(2..=cage.cells.len()) {
+            for subset in combinations(&indexes, size) {
+                let cells: Vec<Point> = subset.iter().map(|index| cage.cells[*index]).collect();
+                let values = cells
+                    .iter()
+                    .fold(CandidateSet::EMPTY, |union, (row, col)| union | notes[*row][*col]);
+                if values.count() != size {
+                    continue;
+                }
+                let targets: Vec<Point> = layout.get_peers(cells[0])
+                    .into_iter()
+                    .filter(|point| cells.iter().all(|cell| layout.sees(*cell, *point)))
+                    .collect();
+                deductions.extend(Deduction::record(technique, &cells, notes, |notes| {
+                    let mut result = false;
+                    for (row, col) in targets {
+                        result |= !(notes[row][col] & values).is_empty();
+                        notes[row][col] -= values;
+                    }
+                    result
+                }));
+            }
+        }
+    }
+    deductions
+}
+
+fn restrict_to_sum<const N: usize>(
+    technique: &'static str,
+    layout: &Layout<N>,
+    notes: &mut Notes<N>,
+    cells: &[Point],
+    sum: usize
+) -> Option<Deduction> {
+    let mut possible = vec![CandidateSet::EMPTY; cells.len()];
+    collect_values(layout, notes, cells, sum, &mut Vec::new(), &mut possible);
+    Deduction::record(technique, cells, notes, |notes| {
+        let mut result = false;
+        for ((row, col), values) in cells.iter().zip(possible) {
+            let note = &mut notes[*row][*col];
+            result |= !note.is_subset(values);
+            *note &= values;
+        }
+        result
+    })
+}
+
+fn collect_values<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    cells: &[Point],
+    sum: usize,
+    assigned: &mut Vec<usize>,
+    possible: &mut [CandidateSet]
+) -> bool {
+    let remaining = &cells[assigned.len()..];
+    let low: usize = remaining.iter().filter_map(|(row, col)| notes[*row][*col].min()).sum();
+    let high: usize = remaining.iter().filter_map(|(row, col)| notes[*row][*col].max()).sum();
+    if sum < low || sum > high {
+        return false;
+    }
+    let Some((row, col)) = remaining.first().copied() else {
+        for (values, value) in possible.iter_mut().zip(assigned.iter()) {
+            values.insert(*value);
+        }
+        return cells.iter().zip(possible.iter()).all(|((row, col), values)| {
+            notes[*row][*col] == *values
+        });
+    };
+    for value in notes[row][col].iter().take_while(|value| *value <= sum) {
+        let is_repeated = assigned
+            .iter()
+            .zip(cells)
+            .any(|(other, cell)| *other == value && layout.sees(*cell, (row, col)));
+        if is_repeated {
+            continue;
+        }
+        assigned.push(value);
+        let is_complete = collect_values(layout, notes, cells, sum - value, assigned, possible);
+        assigned.pop();
+        if is_complete {
+            return true; // every candidate is already known to fit
+        }
+    }
+    false
+}
+
+fn get_unsolved<const N: usize>(
+    notes: &Notes<N>,
+    house: &Coordinates<N>
+) -> Option<(Vec<Point>, usize)> {
+    let cells: Vec<Point> = house
+        .iter()
+        .copied()
+        .filter(|(row, col)| !notes[*row][*col].is_empty())
+        .collect();
+    let values = cells
+        .iter()
+        .fold(CandidateSet::EMPTY, |union, (row, col)| union | notes[*row][*col]);
+    if cells.is_empty() || values.count() != cells.len() {
+        return None;
+    }
+    Some((cells, values.iter().sum()))
+}
+
+fn get_innies<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    house: &Coordinates<N>
+) -> Option<(Vec<Point>, usize)> {
+    let inside: Vec<&Cage> = layout.get_cages()
+        .iter()
+        .filter(|cage| cage.cells.iter().all(|cell| house.contains(cell)))
+        .collect();
+    if inside.is_empty() {
+        return None;
+    }
+    let (unsolved, total) = get_unsolved(notes, house)?;
+    let caged: usize = inside.iter().map(|cage| cage.sum).sum();
+    let innies: Vec<Point> = unsolved
+        .into_iter()
+        .filter(|cell| inside.iter().all(|cage| !cage.cells.contains(cell)))
+        .collect();
+    if innies.is_empty() || innies.len() > MAX_VIRTUAL_CAGE || caged > total {
+        return None;
+    }
+    Some((innies, total - caged))
+}
+
+fn get_outies<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    house: &Coordinates<N>
+) -> Option<(Vec<Point>, usize)> {
+    let touching: Vec<&Cage> = layout.get_cages()
+        .iter()
+        .filter(|cage| cage.cells.iter().any(|cell| house.contains(cell)))
+        .collect();
+    if touching.is_empty() {
+        return None;
+    }
+    let (unsolved, total) = get_unsolved(notes, house)?;
+    let is_covered = unsolved
+        .iter()
+        .all(|cell| touching.iter().any(|cage| cage.cells.contains(cell)));
+    let caged: usize = touching.iter().map(|cage| cage.sum).sum();
+    let outies: Vec<Point> = touching
+        .iter()
+        .flat_map(|cage| cage.cells.iter().copied())
+        .filter(|cell| !house.contains(cell))
+        .collect();
+    if !is_covered || outies.is_empty() || outies.len() > MAX_VIRTUAL_CAGE || caged < total {
+        return None;
+    }
+    Some((outies, caged - total))
+}
+
+#[cfg(test)]
+mod tests {
+    include!("ut/test_cages.rs");
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+use std::ops::{ BitAnd, BitAndAssign, BitOr, BitOrAssign, Sub, SubAssign };
+const CAPACITY: usize = u32::BITS as usize;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CandidateSet(u32);
+
+impl CandidateSet {
+    pub const EMPTY: CandidateSet = CandidateSet(0);
+
+    pub fn full(size: usize) -> Self {
+        CandidateSet(u32::MAX >> (CAPACITY - size))
+    }
+
+    #[cfg(test)]
+    pub const fn from_bits(bits: u32) -> Self {
+        CandidateSet(bits)
+    }
+
+    pub fn from_value(value: usize) -> Self {
+        let mut set = Self::EMPTY;
+        set.insert(value);
+        set
+    }
+
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    pub fn contains(self, value: usize) -> bool {
+        is_valid_value(value) && (self.0 & mask(value)) != 0
+    }
+
+    pub fn insert(&mut self, value: usize) {
+        if is_valid_value(value) {
+            self.0 |= mask(value);
+        }
+    }
+
+    pub fn remove(&mut self, value: usize) -> bool {
+        let present = self.contains(value);
+        if present {
+            self.0 &= !mask(value);
+        }
+        present
+    }
+
+    pub fn count(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn get_single(self) -> Option<usize> {
+        if self.count() == 1 { self.min() } else { None }
+    }
+
+    pub fn min(self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    pub fn max(self) -> Option<usize> {
+        self.iter().last()
+    }
+
+    pub fn union(self, other: CandidateSet) -> Self {
+        CandidateSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: CandidateSet) -> Self {
+        CandidateSet(self.0 & other.0)
+    }
+
+    pub fn difference(self, other: CandidateSet) -> Self {
+        CandidateSet(self.0 & !other.0)
+    }
+
+    pub fn is_subset(self, other: CandidateSet) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = usize> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let value = bits.trailing_zeros() as usize + 1;
+            bits &= bits - 1;
+            Some(value)
+        })
+    }
+}
+
+fn mask(value: usize) -> u32 {
+    1 << (value - 1)
+}
+
+fn is_valid_value(value: usize) -> bool {
+    value > 0 && value <= CAPACITY
+}
+
+pub fn value_to_char(value: usize) -> char {
+    char::from_digit(value as u32, 36).map_or('?', |digit| digit.to_ascii_uppercase())
+}
+
+impl FromIterator<usize> for CandidateSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(values: I) -> Self {
+        let mut set = Self::EMPTY;
+        for value in values {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl BitOr for CandidateSet {
+    type Output = CandidateSet;
+
+    fn bitor(self, other: CandidateSet) -> CandidateSet {
+        self.union(other)
+    }
+}
+
+impl BitOrAssign for CandidateSet {
+    fn bitor_assign(&mut self, other: CandidateSet) {
+        *self = self.union(other);
+    }
+}
+
+impl BitAnd for CandidateSet {
+    type Output = CandidateSet;
+
+    fn bitand(self, other: CandidateSet) -> CandidateSet {
+        self.intersection(other)
+    }
+}
+
+impl BitAndAssign for CandidateSet {
+    fn bitand_assign(&mut self, other: CandidateSet) {
+        *self = self.intersection(other);
+    }
+}
+
+impl Sub for CandidateSet {
+    type Output = CandidateSet;
+
+    fn sub(self, other: CandidateSet) -> CandidateSet {
+        self.difference(other)
+    }
+}
+
+impl SubAssign for CandidateSet {
+    fn sub_assign(&mut self, other: CandidateSet) {
+        *self = self.difference(other);
+    }
+}
+
+impl fmt::Display for CandidateSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for value in self.iter() {
+            write!(f, "{}", value_to_char(value))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::grid_size::GRID_SIZE;
+
+    #[test]
+    fn test_contains() {
+        let set = CandidateSet::from_bits(0b111_000_111);
+        assert!(set.contains(1));
+        assert!(set.contains(8));
+        assert!(!set.contains(4));
+        assert!(!set.contains(0));
+        assert!(!set.contains(GRID_SIZE + 1));
+        assert!(!set.contains(CAPACITY + 1));
+        let full = CandidateSet::full(GRID_SIZE);
+        assert!(full.iter().all(|value| !CandidateSet::EMPTY.contains(value)));
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = CandidateSet::EMPTY;
+        set.insert(3);
+        set.insert(7);
+        set.insert(0);
+        assert_eq!(set, CandidateSet::from_bits(0b001_000_100));
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set, CandidateSet::from_value(7));
+    }
+
+    #[test]
+    fn test_iteration() {
+        let set: CandidateSet = [9, 2, 5].into_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<usize>>(), vec![2, 5, 9]);
+        assert_eq!(set.count(), 3);
+        assert_eq!((set.min(), set.max()), (Some(2), Some(9)));
+        assert_eq!(set.get_single(), None);
+        assert_eq!(CandidateSet::from_value(4).get_single(), Some(4));
+        assert_eq!(CandidateSet::EMPTY.min(), None);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let first = CandidateSet::from_bits(0b000_000_111);
+        let second = CandidateSet::from_bits(0b000_001_100);
+        assert_eq!(first | second, CandidateSet::from_bits(0b000_001_111));
+        assert_eq!(first & second, CandidateSet::from_value(3));
+        assert_eq!(first - second, CandidateSet::from_bits(0b000_000_011));
+        assert!((first & second).is_subset(first));
+        assert!(!second.is_subset(first));
+        assert_eq!(CandidateSet::full(GRID_SIZE), CandidateSet::from_bits(0b111_111_111));
+        assert_eq!(CandidateSet::full(25).count(), 25);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(CandidateSet::from_bits(0b100_010_001).to_string(), "159");
+        assert_eq!(CandidateSet::EMPTY.to_string(), "");
+        assert_eq!([1, 9, 10, 16, 25].into_iter().collect::<CandidateSet>().to_string(), "19AGP");
+    }
+}
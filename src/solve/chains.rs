@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use crate::solve::notes::*;
+use crate::solve::coordinates::*;
+use crate::solve::map_notes::get_strong_links;
+use crate::solve::deduction::Deduction;
+use crate::solve::candidate_set::CandidateSet;
+
+const MAX_CHAIN_LENGTH: usize = 16;
+
+pub type Candidate = (Point, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChainKind {
+    XChain,
+    XyChain,
+    Aic,
+    DiscontinuousNiceLoop,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Chain {
+    pub kind: ChainKind,
+    pub nodes: Vec<Candidate>,
+    pub eliminations: Vec<Candidate>,
+}
+
+impl ChainKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChainKind::XChain => "X-Chain",
+            ChainKind::XyChain => "XY-Chain",
+            ChainKind::Aic => "Alternating Inference Chain",
+            ChainKind::DiscontinuousNiceLoop => "Discontinuous Nice Loop",
+        }
+    }
+}
+
+impl Chain {
+    pub fn clear<const N: usize>(&self, notes: &mut Notes<N>) -> bool {
+        let mut result = false;
+        for (cell, value) in self.eliminations.iter() {
+            result |= clear_value(notes, *value, &[*cell]);
+        }
+        result
+    }
+
+    pub fn apply<const N: usize>(&self, notes: &mut Notes<N>) -> Option<Deduction> {
+        let mut reasons: Vec<Point> = Vec::new();
+        for (cell, _) in self.nodes.iter() {
+            if !reasons.contains(cell) {
+                reasons.push(*cell);
+            }
+        }
+        Deduction::record(self.kind.name(), &reasons, notes, |notes| self.clear(notes))
+    }
+}
+
+struct Graph {
+    candidates: Vec<Candidate>,
+    strong: Vec<Vec<usize>>,
+    weak: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    fn new<const N: usize>(layout: &Layout<N>, notes: &Notes<N>) -> Self {
+        let mut candidates = Vec::new();
+        for (row, notes_row) in notes.iter().enumerate() {
+            for (col, note) in notes_row.iter().enumerate() {
+                for value in note.iter() {
+                    candidates.push(((row, col), value));
+                }
+            }
+        }
+        let indexes: HashMap<Candidate, usize> = candidates
+            .iter()
+            .enumerate()
+            .map(|(index, candidate)| (*candidate, index))
+            .collect();
+
+        let mut strong = vec![Vec::new(); candidates.len()];
+        let mut weak = vec![Vec::new(); candidates.len()];
+        for (index, first) in candidates.iter().enumerate() {
+            for (other, second) in candidates.iter().enumerate() {
+                if index != other && Self::is_weak_link(layout, *first, *second) {
+                    weak[index].push(other);
+                }
+            }
+            let (row, col) = first.0;
+            if notes[row][col].count() == 2 {
+                let other = notes[row][col] - CandidateSet::from_value(first.1);
+                strong[index].extend(other.min().map(|value| indexes[&(first.0, value)]));
+            }
+        }
+        for value in 1..=N {
+            for link in get_strong_links(layout, notes, value) {
+                let first = indexes[&(link.ends[0], value)];
+                let second = indexes[&(link.ends[1], value)];
+                strong[first].push(second);
+                strong[second].push(first);
+            }
+        }
+        Graph { candidates, strong, weak }
+    }
+
+    fn is_weak_link<const N: usize>(
+        layout: &Layout<N>,
+        (first, first_value): Candidate,
+        (second, second_value): Candidate
+    ) -> bool {
+        if first == second {
+            first_value != second_value
+        } else {
+            first_value == second_value && layout.sees(first, second)
+        }
+    }
+
+    fn search(&self, start: usize, assumed: bool) -> Option<Chain> {
+        let states = self.candidates.len();
+        let state = |index: usize, on: bool| index + if on { states } else { 0 };
+        let mut parents: Vec<Option<usize>> = vec![None; 2 * states];
+        let mut depths = vec![0; 2 * states];
+        let mut queue = VecDeque::from([state(start, assumed)]);
+        parents[state(start, assumed)] = Some(state(start, assumed));
+
+        while let Some(current) = queue.pop_front() {
+            let (index, on) = (current % states, current >= states);
+            if current != state(start, assumed) {
+                if let Some(chain) = self.check_chain(start, assumed, index, on, &parents) {
+                    return Some(chain);
+                }
+            }
+            if depths[current] >= MAX_CHAIN_LENGTH {
+                continue;
+            }
+            let links = if on { &self.weak[index] } else { &self.strong[index] };
+            for next in links.iter().map(|next| state(*next, !on)) {
+                if parents[next].is_none() {
+                    parents[next] = Some(current);
+                    depths[next] = depths[current] + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    fn check_chain(
+        &self,
+        start: usize,
+        assumed: bool,
+        end: usize,
+        on: bool,
+        parents: &[Option<usize>]
+    ) -> Option<Chain> {
+        let eliminations: Vec<usize> = match (assumed, on) {
+            (true, false) if end == start => vec![start],
+            (false, true) if end == start => self.weak[start].clone(),
+            (false, true) => {
+                self.weak[start]
+                    .iter()
+                    .filter(|candidate| self.weak[end].contains(candidate))
+                    .copied()
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        if eliminations.is_empty() {
+            return None;
+        }
+        let nodes = self.get_path(end + if on { self.candidates.len() } else { 0 }, parents);
+        Some(Chain {
+            kind: self.get_kind(&nodes, start == end),
+            nodes: nodes.iter().map(|(index, _)| self.candidates[*index]).collect(),
+            eliminations: eliminations.iter().map(|index| self.candidates[*index]).collect(),
+        })
+    }
+
+    fn get_path(&self, end: usize, parents: &[Option<usize>]) -> Vec<(usize, bool)> {
+        let states = self.candidates.len();
+        let mut path = vec![(end % states, end >= states)];
+        let mut current = end;
+        while let Some(parent) = parents[current].filter(|parent| *parent != current) {
+            path.insert(0, (parent % states, parent >= states));
+            current = parent;
+        }
+        path
+    }
+
+    fn get_kind(&self, nodes: &[(usize, bool)], is_loop: bool) -> ChainKind {
+        let candidate = |(index, _): &(usize, bool)| self.candidates[*index];
+        let is_xy_link = |pair: &[(usize, bool)]| {
+            let (first, first_value) = candidate(&pair[0]);
+            let (second, second_value) = candidate(&pair[1]);
+            let is_weak_step = pair[0].1;
+            if is_weak_step {
+                first != second && first_value == second_value
+            } else {
+                first == second
+            }
+        };
+        if is_loop {
+            ChainKind::DiscontinuousNiceLoop
+        } else if nodes.iter().all(|node| candidate(node).1 == candidate(&nodes[0]).1) {
+            ChainKind::XChain
+        } else if nodes.windows(2).all(is_xy_link) {
+            ChainKind::XyChain
+        } else {
+            ChainKind::Aic
+        }
+    }
+}
+
+pub fn use_chains<const N: usize>(layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+    find_chain(layout, notes).and_then(|chain| chain.apply(notes)).into_iter().collect()
+}
+
+pub fn find_chain<const N: usize>(layout: &Layout<N>, notes: &Notes<N>) -> Option<Chain> {
+    let graph = Graph::new(layout, notes);
+    for start in 0..graph.candidates.len() {
+        for assumed in [false, true] {
+            if let Some(chain) = graph.search(start, assumed) {
+                return Some(chain);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::grid_size::GRID_SIZE;
+
+    #[test]
+    fn test_weak_link() {
+        let layout = Layout::<GRID_SIZE>::default();
+        assert!(Graph::is_weak_link(&layout, ((0, 0), 1), ((0, 0), 2)));
+        assert!(Graph::is_weak_link(&layout, ((0, 0), 1), ((0, 7), 1)));
+        assert!(!Graph::is_weak_link(&layout, ((0, 0), 1), ((0, 7), 2)));
+        assert!(!Graph::is_weak_link(&layout, ((0, 0), 1), ((4, 4), 1)));
+    }
+
+    #[test]
+    fn test_xy_chain() {
+        let layout = Layout::default();
+        let mut notes: Notes = [[CandidateSet::full(GRID_SIZE); GRID_SIZE]; GRID_SIZE];
+        notes[0][0] = CandidateSet::from_bits(0b000_000_011);
+        notes[0][4] = CandidateSet::from_bits(0b000_000_110);
+        notes[4][4] = CandidateSet::from_bits(0b000_001_100);
+        notes[4][8] = CandidateSet::from_bits(0b000_001_001);
+        let chain = find_chain(&layout, &notes).unwrap();
+        assert_eq!(chain.kind, ChainKind::XyChain);
+        assert_eq!(chain.nodes, vec![
+            ((0, 0), 1),
+            ((0, 0), 2),
+            ((0, 4), 2),
+            ((0, 4), 3),
+            ((4, 4), 3),
+            ((4, 4), 4),
+            ((4, 8), 4),
+            ((4, 8), 1)
+        ]);
+        assert_eq!(chain.eliminations, vec![((0, 8), 1), ((4, 0), 1)]);
+        assert!(!use_chains(&layout, &mut notes).is_empty());
+        assert_eq!(notes[0][8].bits(), 0b111_111_110);
+        assert_eq!(notes[4][0].bits(), 0b111_111_110);
+    }
+
+    #[test]
+    fn test_x_chain() {
+        let layout = Layout::default();
+        let mut notes: Notes = [[CandidateSet::from_bits(0b111_110_111); GRID_SIZE]; GRID_SIZE];
+        for (row, col) in [(0, 0), (0, 4), (2, 3), (6, 3), (6, 0), (3, 0), (6, 7)] {
+            notes[row][col] = CandidateSet::full(GRID_SIZE);
+        }
+        let chain = find_chain(&layout, &notes).unwrap();
+        assert_eq!(chain.kind, ChainKind::XChain);
+        assert_eq!(chain.nodes, vec![((0, 0), 4), ((0, 4), 4), ((2, 3), 4), ((6, 3), 4)]);
+        assert_eq!(chain.eliminations, vec![((6, 0), 4)]);
+        assert!(chain.clear(&mut notes));
+        assert_eq!(notes[6][0].bits(), 0b111_110_111);
+    }
+
+    #[test]
+    fn test_nice_loop() {
+        let layout = Layout::default();
+        let mut notes: Notes = [[CandidateSet::full(GRID_SIZE); GRID_SIZE]; GRID_SIZE];
+        notes[0][5] = CandidateSet::from_bits(0b000_000_011);
+        notes[4][5] = CandidateSet::from_bits(0b000_000_110);
+        notes[4][0] = CandidateSet::from_bits(0b000_000_101);
+        let chain = find_chain(&layout, &notes).unwrap();
+        assert_eq!(chain.kind, ChainKind::DiscontinuousNiceLoop);
+        assert_eq!(chain.nodes.first(), Some(&((0, 0), 1)));
+        assert_eq!(chain.nodes.last(), Some(&((0, 0), 1)));
+        assert_eq!(chain.eliminations, vec![((0, 0), 1)]);
+    }
+
+    #[test]
+    fn test_no_chain() {
+        let layout = Layout::default();
+        let notes: Notes = [[CandidateSet::full(GRID_SIZE); GRID_SIZE]; GRID_SIZE];
+        assert_eq!(find_chain(&layout, &notes), None);
+    }
+}
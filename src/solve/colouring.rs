@@ -0,0 +1,203 @@
+use crate::solve::notes::*;
+use crate::solve::coordinates::*;
+use crate::solve::map_notes::*;
+use crate::solve::deduction::Deduction;
+
+pub type Cluster = [Vec<Point>; 2];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColouringKind {
+    ColourWrap,
+    ColourTrap,
+    MultiColouring,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Colouring {
+    pub kind: ColouringKind,
+    pub value: usize,
+    pub clusters: Vec<Cluster>,
+    pub eliminations: Vec<Point>,
+}
+
+impl ColouringKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColouringKind::ColourWrap => "Colour Wrap",
+            ColouringKind::ColourTrap => "Colour Trap",
+            ColouringKind::MultiColouring => "Multi-Colouring",
+        }
+    }
+}
+
+impl Colouring {
+    pub fn clear<const N: usize>(&self, notes: &mut Notes<N>) -> bool {
+        clear_value(notes, self.value, &self.eliminations)
+    }
+
+    pub fn apply<const N: usize>(&self, notes: &mut Notes<N>) -> Option<Deduction> {
+        let reasons: Vec<Point> = self.clusters.iter().flatten().flatten().copied().collect();
+        Deduction::record(self.kind.name(), &reasons, notes, |notes| self.clear(notes))
+    }
+}
+
+pub fn use_colouring<const N: usize>(layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+    find_colourings(layout, notes).iter().filter_map(|colouring| colouring.apply(notes)).collect()
+}
+
+pub fn find_colourings<const N: usize>(layout: &Layout<N>, notes: &Notes<N>) -> Vec<Colouring> {
+    let mut found = Vec::new();
+    for value in 1..=N {
+        let clusters = get_clusters(&get_strong_links(layout, notes, value));
+        for cluster in clusters.iter() {
+            found.extend(check_colour_wrap(layout, value, cluster));
+            found.extend(check_colour_trap(layout, notes, value, cluster));
+        }
+        for (index, first) in clusters.iter().enumerate() {
+            for second in clusters[index + 1..].iter() {
+                found.extend(check_multi_colouring(layout, notes, value, first, second));
+            }
+        }
+    }
+    found
+}
+
+pub fn get_clusters(links: &[StrongLink]) -> Vec<Cluster> {
+    let mut clusters = Vec::new();
+    let mut coloured: Vec<Point> = Vec::new();
+    for link in links {
+        let start = link.ends[0];
+        if coloured.contains(&start) {
+            continue;
+        }
+        let mut cluster: Cluster = [vec![start], Vec::new()];
+        let mut queue = vec![(start, 0)];
+        coloured.push(start);
+        while let Some((cell, colour)) = queue.pop() {
+            for other in links.iter().filter_map(|link| get_conjugate(link, cell)) {
+                if !coloured.contains(&other) {
+                    coloured.push(other);
+                    cluster[1 - colour].push(other);
+                    queue.push((other, 1 - colour));
+                }
+            }
+        }
+        clusters.push(cluster);
+    }
+    clusters
+}
+
+fn get_conjugate(link: &StrongLink, cell: Point) -> Option<Point> {
+    match link.ends {
+        [first, second] if first == cell => Some(second),
+        [first, second] if second == cell => Some(first),
+        _ => None,
+    }
+}
+
+fn check_colour_wrap<const N: usize>(
+    layout: &Layout<N>,
+    value: usize,
+    cluster: &Cluster
+) -> Option<Colouring> {
+    for colour in cluster.iter() {
+        if any_sees(layout, colour, colour) {
+            return Some(Colouring {
+                kind: ColouringKind::ColourWrap,
+                value,
+                clusters: vec![cluster.clone()],
+                eliminations: colour.clone(),
+            });
+        }
+    }
+    None
+}
+
+fn check_colour_trap<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize,
+    cluster: &Cluster
+) -> Option<Colouring> {
+    let eliminations = get_seeing_both(layout, notes, value, &cluster[0], &cluster[1], &[cluster]);
+    if eliminations.is_empty() {
+        return None;
+    }
+    Some(Colouring {
+        kind: ColouringKind::ColourTrap,
+        value,
+        clusters: vec![cluster.clone()],
+        eliminations,
+    })
+}
+
+fn check_multi_colouring<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize,
+    first: &Cluster,
+    second: &Cluster
+) -> Vec<Colouring> {
+    let mut found = Vec::new();
+    for (colour, other) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+        if !any_sees(layout, &first[colour], &second[other]) {
+            continue;
+        }
+        let eliminations = if any_sees(layout, &first[colour], &second[1 - other]) {
+            first[colour].clone()
+        } else {
+            get_seeing_both(
+                layout,
+                notes,
+                value,
+                &first[1 - colour],
+                &second[1 - other],
+                &[first, second]
+            )
+        };
+        if !eliminations.is_empty() {
+            found.push(Colouring {
+                kind: ColouringKind::MultiColouring,
+                value,
+                clusters: vec![first.clone(), second.clone()],
+                eliminations,
+            });
+        }
+    }
+    found
+}
+
+fn any_sees<const N: usize>(layout: &Layout<N>, first: &[Point], second: &[Point]) -> bool {
+    first.iter().any(|cell| second.iter().any(|other| layout.sees(*cell, *other)))
+}
+
+fn is_coloured(clusters: &[&Cluster], cell: Point) -> bool {
+    clusters.iter().any(|cluster| cluster.iter().any(|colour| colour.contains(&cell)))
+}
+
+fn get_seeing_both<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize,
+    first: &[Point],
+    second: &[Point],
+    excluded: &[&Cluster]
+) -> Vec<Point> {
+    let mut cells = Vec::new();
+    for (row, notes_row) in notes.iter().enumerate() {
+        for (col, note) in notes_row.iter().enumerate() {
+            let cell = (row, col);
+            let is_candidate = note.contains(value) && !is_coloured(excluded, cell);
+            let sees_both = any_sees(layout, &[cell], first) && any_sees(layout, &[cell], second);
+            if is_candidate && sees_both {
+                cells.push(cell);
+            }
+        }
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    include!("ut/test_colouring.rs");
+}
@@ -1,59 +1,341 @@
-use crate::common::grid_size::GRID_SIZE;
+use serde::Serialize;
+use serde::Deserialize;
+use crate::common::grid_size::*;
+use crate::common::puzzle::Puzzle;
+use crate::solve::cages::Cage;
+use crate::solve::lines::Line;
+use crate::solve::map_notes::House;
+use crate::solve::validation::{ SolverError, check_cages, check_lines };
 
 pub type Point = (usize, usize);
-pub type Coordinates = [Point; GRID_SIZE];
+pub type Coordinates<const N: usize = GRID_SIZE> = [Point; N];
 
-pub fn get_row_coordinates(row: usize) -> Coordinates {
-    let mut c: Coordinates = Default::default();
-    for (i, coord) in c.iter_mut().enumerate() {
-        *coord = (row, i);
-    }
-    c
+pub type RegionMap<const N: usize = GRID_SIZE> = [[usize; N]; N];
+
+type Offset = (isize, isize);
+
+const KNIGHT_MOVES: [Offset; 8] = [
+    (-2, -1), (-2, 1), (-1, -2), (-1, 2),
+    (1, -2), (1, 2), (2, -1), (2, 1),
+];
+const KING_MOVES: [Offset; 8] = [
+    (-1, -1), (-1, 0), (-1, 1), (0, -1),
+    (0, 1), (1, -1), (1, 0), (1, 1),
+];
+const ORTHOGONAL_MOVES: [Offset; 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Variant {
+    Diagonals,
+    Windows,
+    AntiKnight,
+    AntiKing,
+    NonConsecutive,
 }
 
-pub fn get_col_coordinates(col: usize) -> Coordinates {
-    let mut c: Coordinates = Default::default();
-    for (i, coord) in c.iter_mut().enumerate() {
-        *coord = (i, col);
-    }
-    c
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout<const N: usize = GRID_SIZE> {
+    region_map: RegionMap<N>,
+    regions: Vec<Coordinates<N>>,
+    extra_houses: Vec<Coordinates<N>>,
+    cages: Vec<Cage>,
+    lines: Vec<Line>,
+    moves: Vec<Offset>,
+    non_consecutive: bool,
+    solved: Puzzle<N>,
 }
 
-pub fn get_square_coordinates((square_x, square_y): Point) -> Coordinates {
-    let mut c: Coordinates = Default::default();
-    let mut index = 0;
-    for row in [3 * square_x, 3 * square_x + 1, 3 * square_x + 2] {
-        for col in [3 * square_y, 3 * square_y + 1, 3 * square_y + 2] {
-            c[index] = (row, col);
-            index += 1;
+impl<const N: usize> Layout<N> {
+    pub fn new(box_rows: usize, box_cols: usize) -> Self {
+        assert_eq!(box_rows * box_cols, N, "{}x{} boxes don't fit the grid", box_rows, box_cols);
+        let mut region_map = [[0; N]; N];
+        for (row, regions_row) in region_map.iter_mut().enumerate() {
+            for (col, region) in regions_row.iter_mut().enumerate() {
+                *region = col / box_cols * (N / box_rows) + row / box_rows;
+            }
         }
+        Self::from_regions(region_map).unwrap()
+    }
+
+    pub fn from_regions(region_map: RegionMap<N>) -> Result<Self, SolverError> {
+        let mut regions = vec![Vec::new(); N];
+        for (row, regions_row) in region_map.iter().enumerate() {
+            for (col, region) in regions_row.iter().enumerate() {
+                match regions.get_mut(*region) {
+                    Some(cells) => cells.push((row, col)),
+                    None => return Err(SolverError::InvalidRegion { index: *region }),
+                }
+            }
+        }
+        let regions = regions
+            .into_iter()
+            .enumerate()
+            .map(|(index, cells)| {
+                Coordinates::<N>::try_from(cells)
+                    .map_err(|_| SolverError::InvalidRegion { index })
+            })
+            .collect::<Result<Vec<Coordinates<N>>, SolverError>>()?;
+        Ok(Layout {
+            region_map,
+            regions,
+            extra_houses: Vec::new(),
+            cages: Vec::new(),
+            lines: Vec::new(),
+            moves: Vec::new(),
+            non_consecutive: false,
+            solved: [[0; N]; N],
+        })
+    }
+
+    pub fn with_variant(self, variant: Variant) -> Self {
+        match variant {
+            Variant::Diagonals => self.with_diagonals(),
+            Variant::Windows => self.with_windows(),
+            Variant::AntiKnight => self.with_anti_knight(),
+            Variant::AntiKing => self.with_anti_king(),
+            Variant::NonConsecutive => self.with_non_consecutive(),
+        }
+    }
+
+    pub fn with_diagonals(self) -> Self {
+        let mut main: Coordinates<N> = [(0, 0); N];
+        let mut anti: Coordinates<N> = [(0, 0); N];
+        for i in 0..N {
+            main[i] = (i, i);
+            anti[i] = (i, N - 1 - i);
+        }
+        self.with_house(main).with_house(anti)
+    }
+
+    pub fn with_windows(mut self) -> Self {
+        let (box_rows, box_cols) = get_box_size(N);
+        for top in (1..=N - box_rows).step_by(box_rows + 1) {
+            for left in (1..=N - box_cols).step_by(box_cols + 1) {
+                let mut window: Coordinates<N> = [(0, 0); N];
+                for (i, coord) in window.iter_mut().enumerate() {
+                    *coord = (top + i / box_cols, left + i % box_cols);
+                }
+                self = self.with_house(window);
+            }
+        }
+        self
+    }
+
+    fn with_house(mut self, house: Coordinates<N>) -> Self {
+        self.extra_houses.push(house);
+        self
+    }
+
+    pub fn with_anti_knight(self) -> Self {
+        self.with_moves(&KNIGHT_MOVES)
+    }
+
+    pub fn with_anti_king(self) -> Self {
+        self.with_moves(&KING_MOVES)
+    }
+
+    pub fn with_non_consecutive(mut self) -> Self {
+        self.non_consecutive = true;
+        self
+    }
+
+    fn with_moves(mut self, moves: &[Offset]) -> Self {
+        for offset in moves.iter() {
+            if !self.moves.contains(offset) {
+                self.moves.push(*offset);
+            }
+        }
+        self
+    }
+
+    pub fn with_cages(mut self, cages: Vec<Cage>) -> Result<Self, SolverError> {
+        self.cages.extend(cages);
+        check_cages::<N>(&self.cages)?;
+        Ok(self)
+    }
+
+    pub fn with_lines(mut self, lines: Vec<Line>) -> Result<Self, SolverError> {
+        self.lines.extend(lines);
+        check_lines::<N>(&self.lines)?;
+        Ok(self)
+    }
+
+    pub fn without_solved(&self, puzzle: &Puzzle<N>) -> Self {
+        let cages = self.cages
+            .iter()
+            .map(|cage| cage.without_solved(puzzle))
+            .filter(|cage| !cage.cells.is_empty())
+            .collect();
+        let lines = self.lines
+            .iter()
+            .filter(|line| line.cells.iter().any(|(row, col)| puzzle[*row][*col] == 0))
+            .cloned()
+            .collect();
+        Layout { cages, lines, solved: *puzzle, ..self.clone() }
+    }
+
+    pub fn get_row_coordinates(&self, row: usize) -> Coordinates<N> {
+        let mut c: Coordinates<N> = [(0, 0); N];
+        for (i, coord) in c.iter_mut().enumerate() {
+            *coord = (row, i);
+        }
+        c
+    }
+
+    pub fn get_col_coordinates(&self, col: usize) -> Coordinates<N> {
+        let mut c: Coordinates<N> = [(0, 0); N];
+        for (i, coord) in c.iter_mut().enumerate() {
+            *coord = (i, col);
+        }
+        c
+    }
+
+    pub fn get_region_coordinates(&self, region: usize) -> Coordinates<N> {
+        self.regions[region]
+    }
+
+    pub fn get_region(&self, (row, col): Point) -> usize {
+        self.region_map[row][col]
+    }
+
+    pub fn get_houses(&self, index: usize) -> [(House, Coordinates<N>); 3] {
+        [
+            (House::Row, self.get_row_coordinates(index)),
+            (House::Col, self.get_col_coordinates(index)),
+            (House::Region, self.get_region_coordinates(index)),
+        ]
+    }
+
+    pub fn get_extra_houses(&self) -> &[Coordinates<N>] {
+        &self.extra_houses
+    }
+
+    pub fn get_cages(&self) -> &[Cage] {
+        &self.cages
     }
-    c
-}
 
-pub fn perform_for_all_sets<F>(mut action: F) -> bool where F: FnMut(&Coordinates) -> bool {
-    let mut result = false;
-    for index in 0..GRID_SIZE {
-        let all_coordinates = [
-            get_row_coordinates(index),
-            get_col_coordinates(index),
-            get_square_coordinates((index % 3, index / 3)),
+    pub fn get_lines(&self) -> &[Line] {
+        &self.lines
+    }
+
+    pub fn get_solved(&self, (row, col): Point) -> u8 {
+        self.solved[row][col]
+    }
+
+    pub fn has_chess_constraints(&self) -> bool {
+        !self.moves.is_empty() || self.non_consecutive
+    }
+
+    pub fn get_moves(&self, cell: Point) -> Vec<Point> {
+        get_offset_cells::<N>(cell, &self.moves)
+    }
+
+    pub fn get_neighbours(&self, cell: Point) -> Vec<Point> {
+        if !self.non_consecutive {
+            return Vec::new();
+        }
+        get_offset_cells::<N>(cell, &ORTHOGONAL_MOVES)
+    }
+
+    pub fn get_peers(&self, (row, col): Point) -> Vec<Point> {
+        let mut peers = Vec::new();
+        let houses = [
+            self.get_row_coordinates(row),
+            self.get_col_coordinates(col),
+            self.get_region_coordinates(self.get_region((row, col))),
         ];
-        for coordinates in all_coordinates {
-            result |= action(&coordinates);
+        let extra_houses = self.extra_houses.iter().filter(|house| house.contains(&(row, col)));
+        let cages = self.cages
+            .iter()
+            .filter(|cage| cage.cells.contains(&(row, col)))
+            .map(|cage| cage.cells.as_slice());
+        let moves = self.get_moves((row, col));
+        let groups = houses
+            .iter()
+            .chain(extra_houses)
+            .map(|house| house.as_slice())
+            .chain(cages)
+            .chain([moves.as_slice()]);
+        for coordinates in groups {
+            for point in coordinates.iter().copied() {
+                if point != (row, col) && !peers.contains(&point) {
+                    peers.push(point);
+                }
+            }
         }
+        peers
+    }
+
+    pub fn sees(&self, first: Point, second: Point) -> bool {
+        let same_region = self.get_region(first) == self.get_region(second);
+        let same_extra_house = self.extra_houses
+            .iter()
+            .any(|house| house.contains(&first) && house.contains(&second));
+        let same_cage = self.cages
+            .iter()
+            .any(|cage| cage.cells.contains(&first) && cage.cells.contains(&second));
+        let offset = (
+            second.0 as isize - first.0 as isize,
+            second.1 as isize - first.1 as isize,
+        );
+        let same_house = first.0 == second.0 || first.1 == second.1 || same_region;
+        let is_move = self.moves.contains(&offset);
+        first != second && (same_house || same_extra_house || same_cage || is_move)
+    }
+
+    pub fn perform_for_all_sets<F>(&self, mut action: F) -> bool
+        where F: FnMut(&Coordinates<N>) -> bool
+    {
+        let mut result = false;
+        for index in 0..N {
+            for (_, coordinates) in self.get_houses(index) {
+                result |= action(&coordinates);
+            }
+        }
+        for coordinates in self.extra_houses.iter() {
+            result |= action(coordinates);
+        }
+        result
+    }
+}
+
+fn get_offset_cells<const N: usize>((row, col): Point, offsets: &[Offset]) -> Vec<Point> {
+    offsets
+        .iter()
+        .filter_map(|(row_offset, col_offset)| {
+            Some((row.checked_add_signed(*row_offset)?, col.checked_add_signed(*col_offset)?))
+        })
+        .filter(|(row, col)| *row < N && *col < N)
+        .collect()
+}
+
+impl<const N: usize> Default for Layout<N> {
+    fn default() -> Self {
+        let (box_rows, box_cols) = get_box_size(N);
+        Layout::new(box_rows, box_cols)
     }
-    result
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const JIGSAW_REGIONS: RegionMap = [
+        [0, 0, 0, 1, 1, 2, 2, 2, 2],
+        [0, 0, 0, 1, 1, 1, 2, 2, 2],
+        [0, 0, 3, 1, 1, 1, 1, 2, 2],
+        [0, 3, 3, 4, 4, 4, 4, 5, 5],
+        [3, 3, 3, 4, 4, 5, 5, 5, 5],
+        [3, 3, 3, 4, 4, 7, 5, 5, 5],
+        [6, 6, 6, 7, 4, 7, 8, 8, 8],
+        [6, 6, 6, 7, 7, 7, 8, 8, 8],
+        [6, 6, 6, 7, 7, 7, 8, 8, 8],
+    ];
+
     #[test]
     fn test_get_row_coordinates() {
         let row = 2;
-        let coordinates = get_row_coordinates(row);
+        let coordinates = Layout::<GRID_SIZE>::default().get_row_coordinates(row);
         for (i, (x, y)) in coordinates.iter().enumerate() {
             assert_eq!(*x, row);
             assert_eq!(*y, i);
@@ -63,7 +345,7 @@ mod tests {
     #[test]
     fn test_get_col_coordinates() {
         let col = 3;
-        let coordinates = get_col_coordinates(col);
+        let coordinates = Layout::<GRID_SIZE>::default().get_col_coordinates(col);
         for (i, (x, y)) in coordinates.iter().enumerate() {
             assert_eq!(*x, i);
             assert_eq!(*y, col);
@@ -71,8 +353,7 @@ mod tests {
     }
 
     #[test]
-    fn test_get_square_coordinates() {
-        let square_coordinates = (0, 2);
+    fn test_get_region_coordinates() {
         let expected: Coordinates = [
             (0, 6),
             (0, 7),
@@ -84,7 +365,129 @@ mod tests {
             (2, 7),
             (2, 8),
         ];
-        let actual = get_square_coordinates(square_coordinates);
+        let actual = Layout::default().get_region_coordinates(6);
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_get_peers() {
+        let peers = Layout::<GRID_SIZE>::default().get_peers((4, 4));
+        assert_eq!(peers.len(), 20);
+        assert!(peers.contains(&(4, 0)));
+        assert!(peers.contains(&(8, 4)));
+        assert!(peers.contains(&(3, 5)));
+        assert!(!peers.contains(&(4, 4)));
+        assert!(!peers.contains(&(2, 2)));
+    }
+
+    #[test]
+    fn test_sees() {
+        let layout = Layout::<GRID_SIZE>::default();
+        assert!(layout.sees((0, 0), (0, 8)));
+        assert!(layout.sees((0, 0), (8, 0)));
+        assert!(layout.sees((0, 0), (2, 2)));
+        assert!(!layout.sees((0, 0), (3, 3)));
+        assert!(!layout.sees((0, 0), (0, 0)));
+    }
+
+    #[test]
+    fn test_box_sizes() {
+        assert_eq!(get_box_size(4), (2, 2));
+        assert_eq!(get_box_size(6), (2, 3));
+        assert_eq!(get_box_size(GRID_SIZE), (3, 3));
+        assert_eq!(get_box_size(12), (3, 4));
+        assert_eq!(get_box_size(16), (4, 4));
+        assert_eq!(get_box_size(25), (5, 5));
+        assert_eq!(Layout::<6>::default(), Layout::new(2, 3));
+    }
+
+    #[test]
+    fn test_rectangular_squares() {
+        let layout = Layout::<6>::default();
+        let expected = [(2, 3), (2, 4), (2, 5), (3, 3), (3, 4), (3, 5)];
+        assert_eq!(layout.get_region_coordinates(4), expected);
+        assert_eq!(layout.get_region((5, 2)), 2);
+        assert!(layout.sees((0, 0), (1, 2)));
+        assert!(!layout.sees((0, 0), (2, 1)));
+        assert_eq!(layout.get_peers((0, 0)).len(), 12);
+        let tall = Layout::<6>::new(3, 2);
+        assert!(tall.sees((0, 0), (2, 1)));
+        assert!(!tall.sees((0, 0), (1, 2)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_boxes() {
+        Layout::<9>::new(2, 4);
+    }
+
+    #[test]
+    fn test_extra_houses() {
+        let layout = Layout::<GRID_SIZE>::default().with_diagonals();
+        assert_eq!(layout.get_extra_houses().len(), 2);
+        assert!(layout.sees((0, 0), (8, 8)));
+        assert!(layout.sees((0, 8), (8, 0)));
+        assert!(!layout.sees((0, 0), (8, 7)));
+        assert_eq!(layout.get_peers((4, 4)).len(), 32);
+        let layout = Layout::<GRID_SIZE>::default().with_windows();
+        assert_eq!(layout.get_extra_houses().len(), 4);
+        assert_eq!(layout.get_extra_houses()[3][8], (7, 7));
+        assert!(layout.sees((1, 1), (3, 3)));
+        assert!(layout.sees((5, 5), (7, 7)));
+        assert!(!layout.sees((1, 1), (5, 5)));
+        assert_eq!(Layout::<4>::default().with_windows().get_extra_houses().len(), 1);
+    }
+
+    #[test]
+    fn test_cages() {
+        let cage = Cage { cells: vec![(0, 0), (3, 3), (3, 4)], sum: 12 };
+        let layout = Layout::<GRID_SIZE>::default().with_cages(vec![cage]).unwrap();
+        assert!(layout.sees((0, 0), (3, 3)));
+        assert!(!layout.sees((0, 0), (4, 4)));
+        assert_eq!(layout.get_peers((0, 0)).len(), 22);
+        let mut puzzle = [[0; GRID_SIZE]; GRID_SIZE];
+        puzzle[3][3] = 5;
+        let reduced = layout.without_solved(&puzzle);
+        assert_eq!(reduced.get_cages()[0], Cage { cells: vec![(0, 0), (3, 4)], sum: 7 });
+        puzzle[0][0] = 3;
+        puzzle[3][4] = 4;
+        assert!(layout.without_solved(&puzzle).get_cages().is_empty());
+    }
+
+    #[test]
+    fn test_chess_moves() {
+        let layout = Layout::<GRID_SIZE>::default().with_anti_knight();
+        assert!(layout.sees((0, 0), (1, 2)));
+        assert!(layout.sees((4, 4), (2, 5)));
+        assert_eq!(layout.get_moves((0, 0)), vec![(1, 2), (2, 1)]);
+        assert_eq!(layout.get_peers((4, 4)).len(), 28);
+        let layout = Layout::<GRID_SIZE>::default().with_anti_king().with_anti_king();
+        assert!(layout.sees((2, 2), (3, 3)));
+        assert!(!layout.sees((2, 2), (4, 4)));
+        assert_eq!(layout.get_moves((0, 0)).len(), 3);
+        assert!(layout.get_neighbours((0, 0)).is_empty());
+        let layout = Layout::<GRID_SIZE>::default().with_non_consecutive();
+        assert!(!layout.sees((2, 2), (3, 3)));
+        assert_eq!(layout.get_peers((4, 4)).len(), 20);
+        assert_eq!(layout.get_neighbours((0, 8)), vec![(0, 7), (1, 8)]);
+        assert!(layout.has_chess_constraints());
+        assert!(!Layout::<GRID_SIZE>::default().has_chess_constraints());
+    }
+
+    #[test]
+    fn test_irregular_regions() {
+        let layout = Layout::from_regions(JIGSAW_REGIONS).unwrap();
+        let expected = [(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2), (2, 0), (2, 1), (3, 0)];
+        assert_eq!(layout.get_region_coordinates(0), expected);
+        assert_eq!(layout.get_region((2, 2)), 3);
+        assert!(layout.sees((3, 0), (0, 2)));
+        assert!(!layout.sees((3, 0), (2, 2)));
+        assert_eq!(layout.get_houses(5)[2].0, House::Region);
+        let mut regions = JIGSAW_REGIONS;
+        regions[8][8] = 7;
+        assert_eq!(Layout::from_regions(regions), Err(SolverError::InvalidRegion { index: 7 }));
+        regions[8][8] = GRID_SIZE;
+        let expected = Err(SolverError::InvalidRegion { index: GRID_SIZE });
+        assert_eq!(Layout::from_regions(regions), expected);
+    }
 }
@@ -0,0 +1,357 @@
+use crate::common::grid_size::GRID_SIZE;
+use crate::common::puzzle::Puzzle;
+use crate::solve::coordinates::*;
+use crate::solve::notes::*;
+use crate::solve::candidate_set::CandidateSet;
+
+const ROOT: usize = 0;
+
+type Choice = (usize, usize, u8);
+
+pub struct DancingLinks<const N: usize = GRID_SIZE> {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    choice: Vec<Choice>,
+    size: Vec<usize>,
+    layout: Layout<N>,
+    peers: Vec<Vec<Point>>,
+    cages: Vec<Vec<usize>>,
+    lines: Vec<Vec<usize>>,
+    grid: Puzzle<N>,
+    notes: Notes<N>,
+    history: Vec<(Puzzle<N>, Notes<N>)>,
+}
+
+impl<const N: usize> DancingLinks<N> {
+    pub fn new(layout: &Layout<N>, puzzle: &Puzzle<N>) -> Self {
+        let headers = 4 * N * N + layout.get_extra_houses().len() * N + 1;
+        let mut links = DancingLinks {
+            left: (0..headers).map(|index| (index + headers - 1) % headers).collect(),
+            right: (0..headers).map(|index| (index + 1) % headers).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            column: (0..headers).collect(),
+            choice: vec![(0, 0, 0); headers],
+            size: vec![0; headers],
+            layout: layout.clone(),
+            peers: (0..N * N).map(|index| layout.get_peers((index / N, index % N))).collect(),
+            cages: vec![Vec::new(); N * N],
+            lines: vec![Vec::new(); N * N],
+            grid: *puzzle,
+            notes: [[CandidateSet::EMPTY; N]; N],
+            history: Vec::new(),
+        };
+        for row in 0..N {
+            for col in 0..N {
+                if puzzle[row][col] != 0 {
+                    continue;
+                }
+                let peers = &links.peers[row * N + col];
+                let cages = layout.get_cages().iter().map(|cage| cage.cells.as_slice());
+                links.cages[row * N + col] = Self::get_affected((row, col), peers, cages);
+                let lines = layout.get_lines().iter().map(|line| line.cells.as_slice());
+                links.lines[row * N + col] = Self::get_affected((row, col), peers, lines);
+                let note = &mut links.notes[row][col];
+                *note = CandidateSet::full(N);
+                for (peer_row, peer_col) in links.peers[row * N + col].iter() {
+                    note.remove(puzzle[*peer_row][*peer_col] as usize);
+                }
+                for (other_row, other_col) in layout.get_neighbours((row, col)) {
+                    let value = puzzle[other_row][other_col] as usize;
+                    if value != 0 {
+                        note.remove(value - 1);
+                        note.remove(value + 1);
+                    }
+                }
+            }
+        }
+        for row in 0..N {
+            for col in 0..N {
+                for value in 1..=N as u8 {
+                    if Self::is_allowed(puzzle, &links.notes, (row, col), value)
+                        && links.fits((row, col, value))
+                    {
+                        links.add_row(layout, (row, col, value));
+                    }
+                }
+            }
+        }
+        links
+    }
+
+    fn is_allowed(puzzle: &Puzzle<N>, notes: &Notes<N>, (row, col): Point, value: u8) -> bool {
+        if puzzle[row][col] != 0 {
+            return puzzle[row][col] == value;
+        }
+        notes[row][col].contains(value as usize)
+    }
+
+    fn get_affected<'a, I>(cell: Point, peers: &[Point], groups: I) -> Vec<usize>
+        where I: Iterator<Item = &'a [Point]>
+    {
+        groups
+            .enumerate()
+            .filter(|(_, cells)| {
+                cells.contains(&cell) || cells.iter().any(|other| peers.contains(other))
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn fits(&mut self, choice: Choice) -> bool {
+        self.save();
+        let fits = self.place(choice);
+        self.restore();
+        fits
+    }
+
+    fn save(&mut self) {
+        self.history.push((self.grid, self.notes));
+    }
+
+    fn restore(&mut self) {
+        if let Some((grid, notes)) = self.history.pop() {
+            (self.grid, self.notes) = (grid, notes);
+        }
+    }
+
+    fn place(&mut self, (row, col, value): Choice) -> bool {
+        if self.grid[row][col] != 0 {
+            return true;
+        }
+        if !self.notes[row][col].contains(value as usize) {
+            return false; // ruled out by a relation the columns don't cover
+        }
+        self.grid[row][col] = value;
+        self.notes[row][col] = CandidateSet::EMPTY;
+        clear_value(&mut self.notes, value as usize, &self.peers[row * N + col]);
+        clear_consecutive(&mut self.notes, value as usize, &self.layout.get_neighbours((row, col)));
+        let (cages, lines) = (self.layout.get_cages(), self.layout.get_lines());
+        self.cages[row * N + col].iter().all(|index| cages[*index].fits(&self.grid, &self.notes))
+            && self.lines[row * N + col].iter().all(|index| {
+                lines[*index].fits(&self.layout, &self.grid, &self.notes)
+            })
+    }
+
+    fn add_row(&mut self, layout: &Layout<N>, (row, col, value): Choice) {
+        let digit = (value - 1) as usize;
+        let region = layout.get_region((row, col));
+        let mut columns = vec![
+            row * N + col,
+            N * N + row * N + digit,
+            2 * N * N + col * N + digit,
+            3 * N * N + region * N + digit,
+        ];
+        for (index, house) in layout.get_extra_houses().iter().enumerate() {
+            if house.contains(&(row, col)) {
+                columns.push(4 * N * N + index * N + digit);
+            }
+        }
+        let first = self.column.len();
+        for (index, column) in columns.iter().map(|column| column + 1).enumerate() {
+            let node = first + index;
+            self.left.push(if index == 0 { first + columns.len() - 1 } else { node - 1 });
+            self.right.push(if index == columns.len() - 1 { first } else { node + 1 });
+            self.up.push(self.up[column]);
+            self.down.push(column);
+            self.down[self.up[column]] = node;
+            self.up[column] = node;
+            self.column.push(column);
+            self.choice.push((row, col, value));
+            self.size[column] += 1;
+        }
+    }
+
+    pub fn find_solutions(&mut self, limit: usize) -> Vec<Puzzle<N>> {
+        let mut solutions = Vec::new();
+        self.search(&mut Vec::new(), &mut solutions, limit);
+        solutions
+    }
+
+    fn search(&mut self, partial: &mut Vec<usize>, solutions: &mut Vec<Puzzle<N>>, limit: usize) {
+        if solutions.len() >= limit {
+            return;
+        }
+        if self.right[ROOT] == ROOT {
+            solutions.push(self.to_puzzle(partial));
+            return;
+        }
+        let column = self.get_smallest_column();
+        self.cover(column);
+        let mut node = self.down[column];
+        while node != column {
+            self.save();
+            if self.place(self.choice[node]) {
+                partial.push(node);
+                self.for_each_in_row(node, |links, other| links.cover(links.column[other]));
+                self.search(partial, solutions, limit);
+                self.for_each_in_row_reversed(node, |links, other| {
+                    links.uncover(links.column[other])
+                });
+                partial.pop();
+            }
+            self.restore();
+            node = self.down[node];
+        }
+        self.uncover(column);
+    }
+
+    fn get_smallest_column(&self) -> usize {
+        let mut best = self.right[ROOT];
+        let mut column = best;
+        while column != ROOT {
+            if self.size[column] < self.size[best] {
+                best = column;
+            }
+            column = self.right[column];
+        }
+        best
+    }
+
+    fn for_each_in_row<F>(&mut self, node: usize, mut action: F) where F: FnMut(&mut Self, usize) {
+        let mut other = self.right[node];
+        while other != node {
+            action(self, other);
+            other = self.right[other];
+        }
+    }
+
+    fn for_each_in_row_reversed<F>(&mut self, node: usize, mut action: F)
+        where F: FnMut(&mut Self, usize)
+    {
+        let mut other = self.left[node];
+        while other != node {
+            action(self, other);
+            other = self.left[other];
+        }
+    }
+
+    fn cover(&mut self, column: usize) {
+        self.right[self.left[column]] = self.right[column];
+        self.left[self.right[column]] = self.left[column];
+        let mut row = self.down[column];
+        while row != column {
+            let mut node = self.right[row];
+            while node != row {
+                self.down[self.up[node]] = self.down[node];
+                self.up[self.down[node]] = self.up[node];
+                self.size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    fn uncover(&mut self, column: usize) {
+        let mut row = self.up[column];
+        while row != column {
+            let mut node = self.left[row];
+            while node != row {
+                self.size[self.column[node]] += 1;
+                self.down[self.up[node]] = node;
+                self.up[self.down[node]] = node;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+        self.right[self.left[column]] = column;
+        self.left[self.right[column]] = column;
+    }
+
+    fn to_puzzle(&self, partial: &[usize]) -> Puzzle<N> {
+        let mut puzzle: Puzzle<N> = [[0; N]; N];
+        for node in partial.iter() {
+            let (row, col, value) = self.choice[*node];
+            puzzle[row][col] = value;
+        }
+        puzzle
+    }
+}
+
+pub fn solve<const N: usize>(layout: &Layout<N>, puzzle: &Puzzle<N>) -> Option<Puzzle<N>> {
+    DancingLinks::new(layout, puzzle).find_solutions(1).pop()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: Puzzle = [
+        [8, 0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 3, 6, 0, 0, 0, 0, 0],
+        [0, 7, 0, 0, 9, 0, 2, 0, 0],
+        [0, 5, 0, 0, 0, 7, 0, 0, 0],
+        [0, 0, 0, 0, 4, 5, 7, 0, 0],
+        [0, 0, 0, 1, 0, 0, 0, 3, 0],
+        [0, 0, 1, 0, 0, 0, 0, 6, 8],
+        [0, 0, 8, 5, 0, 0, 0, 1, 0],
+        [0, 9, 0, 0, 0, 0, 4, 0, 0],
+    ];
+
+    const SOLUTION: Puzzle = [
+        [8, 1, 2, 7, 5, 3, 6, 4, 9],
+        [9, 4, 3, 6, 8, 2, 1, 7, 5],
+        [6, 7, 5, 4, 9, 1, 2, 8, 3],
+        [1, 5, 4, 2, 3, 7, 8, 9, 6],
+        [3, 6, 9, 8, 4, 5, 7, 2, 1],
+        [2, 8, 7, 1, 6, 9, 5, 3, 4],
+        [5, 2, 1, 9, 7, 4, 3, 6, 8],
+        [4, 3, 8, 5, 2, 6, 9, 1, 7],
+        [7, 9, 6, 3, 1, 8, 4, 5, 2],
+    ];
+
+    fn is_valid<const N: usize>(layout: &Layout<N>, solution: &Puzzle<N>) -> bool {
+        !layout.perform_for_all_sets(|coordinates| {
+            let mut house: Vec<u8> = coordinates
+                .iter()
+                .map(|(row, col)| solution[*row][*col])
+                .collect();
+            house.sort();
+            house != (1..=N as u8).collect::<Vec<u8>>()
+        })
+    }
+
+    fn check_empty_grid<const N: usize>(layout: Layout<N>) {
+        let solutions = DancingLinks::new(&layout, &[[0; N]; N]).find_solutions(2);
+        assert_eq!(solutions.len(), 2);
+        assert_ne!(solutions[0], solutions[1]);
+        assert!(solutions.iter().all(|solution| is_valid(&layout, solution)));
+    }
+
+    #[test]
+    fn test_solve() {
+        let layout = Layout::default();
+        assert_eq!(solve(&layout, &PUZZLE), Some(SOLUTION));
+        assert_eq!(solve(&layout, &SOLUTION), Some(SOLUTION));
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        check_empty_grid(Layout::<GRID_SIZE>::default());
+    }
+
+    #[test]
+    fn test_other_sizes() {
+        check_empty_grid(Layout::<4>::default());
+        check_empty_grid(Layout::<6>::default());
+        check_empty_grid(Layout::<6>::new(3, 2));
+        check_empty_grid(Layout::<12>::default());
+        check_empty_grid(Layout::<16>::default());
+        check_empty_grid(Layout::<25>::default());
+    }
+
+    #[test]
+    fn test_invalid_puzzle() {
+        let layout = Layout::default();
+        let mut puzzle = PUZZLE;
+        puzzle[0][8] = 8;
+        assert_eq!(solve(&layout, &puzzle), None);
+        puzzle[0][8] = 0;
+        puzzle[0][1] = 2;
+        puzzle[0][2] = 1;
+        assert_eq!(solve(&layout, &puzzle), None);
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt;
+use crate::common::puzzle::Puzzle;
+use crate::solve::notes::*;
+use crate::solve::coordinates::*;
+use crate::solve::chains::Candidate;
+
+pub const SOLVED_PEERS: &str = "Solved Cell";
+pub const NAKED_SINGLE: &str = "Naked Single";
+pub const HIDDEN_SINGLE: &str = "Hidden Single";
+pub const GUESS: &str = "Guess";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deduction {
+    pub technique: &'static str,
+    pub placements: Vec<(Point, u8)>,
+    pub eliminations: Vec<Candidate>,
+    pub reasons: Vec<Point>,
+}
+
+impl Deduction {
+    pub fn placement(technique: &'static str, cell: Point, value: u8) -> Self {
+        Deduction {
+            technique,
+            placements: vec![(cell, value)],
+            eliminations: Vec::new(),
+            reasons: Vec::new(),
+        }
+    }
+
+    pub fn guess<const N: usize>(puzzle: &Puzzle<N>, solution: &Puzzle<N>) -> Self {
+        let mut placements = Vec::new();
+        for (row, puzzle_row) in puzzle.iter().enumerate() {
+            for (col, value) in puzzle_row.iter().enumerate() {
+                if *value == 0 {
+                    placements.push(((row, col), solution[row][col]));
+                }
+            }
+        }
+        Deduction {
+            technique: GUESS,
+            placements,
+            eliminations: Vec::new(),
+            reasons: Vec::new(),
+        }
+    }
+
+    pub fn record<const N: usize, F>(
+        technique: &'static str,
+        reasons: &[Point],
+        notes: &mut Notes<N>,
+        clear: F
+    ) -> Option<Self>
+        where F: FnOnce(&mut Notes<N>) -> bool
+    {
+        let before = *notes;
+        if !clear(notes) {
+            return None;
+        }
+        Some(Deduction {
+            technique,
+            placements: Vec::new(),
+            eliminations: get_eliminations(&before, notes),
+            reasons: reasons.to_vec(),
+        })
+    }
+
+    #[cfg(test)]
+    pub fn apply<const N: usize>(
+        &self,
+        layout: &Layout<N>,
+        puzzle: &mut Puzzle<N>,
+        notes: &mut Notes<N>
+    ) {
+        for ((row, col), value) in self.placements.iter() {
+            puzzle[*row][*col] = *value;
+            notes[*row][*col] = Default::default();
+            clear_placed(layout, notes, (*row, *col), *value as usize);
+        }
+        for (cell, value) in self.eliminations.iter() {
+            clear_value(notes, *value, &[*cell]);
+        }
+    }
+}
+
+fn get_eliminations<const N: usize>(before: &Notes<N>, after: &Notes<N>) -> Vec<Candidate> {
+    let mut eliminations = Vec::new();
+    for row in 0..N {
+        for col in 0..N {
+            for value in (before[row][col] - after[row][col]).iter() {
+                eliminations.push(((row, col), value));
+            }
+        }
+    }
+    eliminations
+}
+
+impl fmt::Display for Deduction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.technique)?;
+        if !self.reasons.is_empty() {
+            write!(f, " on {}", join_cells(&self.reasons))?;
+        }
+        let mut actions = Vec::new();
+        if !self.placements.is_empty() {
+            let placed: Vec<String> = self.placements
+                .iter()
+                .map(|(cell, value)| format!("{} at {}", value, display_cell(*cell)))
+                .collect();
+            actions.push(format!("places {}", join_words(&placed)));
+        }
+        let mut values: Vec<usize> = self.eliminations.iter().map(|(_, value)| *value).collect();
+        values.sort();
+        values.dedup();
+        let mut removed = Vec::new();
+        for value in values {
+            let cells: Vec<Point> = self.eliminations
+                .iter()
+                .filter(|(_, eliminated)| *eliminated == value)
+                .map(|(cell, _)| *cell)
+                .collect();
+            if !cells.is_empty() {
+                removed.push(format!("{} from {}", value, join_cells(&cells)));
+            }
+        }
+        if !removed.is_empty() {
+            actions.push(format!("removes {}", join_words(&removed)));
+        }
+        write!(f, " {}.", join_words(&actions))
+    }
+}
+
+fn display_cell((row, col): Point) -> String {
+    format!("r{}c{}", row + 1, col + 1)
+}
+
+fn join_cells(cells: &[Point]) -> String {
+    cells.iter().map(|cell| display_cell(*cell)).collect::<Vec<String>>().join(", ")
+}
+
+fn join_words(words: &[String]) -> String {
+    match words.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        Some((last, _)) => last.clone(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::grid_size::GRID_SIZE;
+    use crate::solve::candidate_set::CandidateSet;
+
+    #[test]
+    fn test_record() {
+        let mut notes: Notes = Default::default();
+        notes[0][6] = CandidateSet::from_bits(0b000_010_100);
+        notes[1][1] = CandidateSet::from_bits(0b000_010_000);
+        let deduction = Deduction::record("Naked Pair", &[(0, 0), (0, 1)], &mut notes, |notes| {
+            notes[0][6] = CandidateSet::from_bits(0b000_010_000);
+            notes[1][1] = CandidateSet::EMPTY;
+            true
+        });
+        let expected = Deduction {
+            technique: "Naked Pair",
+            placements: Vec::new(),
+            eliminations: vec![((0, 6), 3), ((1, 1), 5)],
+            reasons: vec![(0, 0), (0, 1)],
+        };
+        assert_eq!(deduction, Some(expected));
+    }
+
+    #[test]
+    fn test_record_without_progress() {
+        let mut notes: Notes = Default::default();
+        assert_eq!(Deduction::record("X-Wing", &[], &mut notes, |_| false), None);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut puzzle: Puzzle = Default::default();
+        let mut notes: Notes = [[CandidateSet::from_bits(0b000_000_011); GRID_SIZE]; GRID_SIZE];
+        let layout = Layout::default();
+        Deduction::placement("Naked Single", (0, 0), 1).apply(&layout, &mut puzzle, &mut notes);
+        assert_eq!(puzzle[0][0], 1);
+        assert!(notes[0][0].is_empty());
+        assert_eq!(notes[0][8].bits(), 0b000_000_010);
+        assert_eq!(notes[2][2].bits(), 0b000_000_010);
+        assert_eq!(notes[3][3].bits(), 0b000_000_011);
+        let elimination = Deduction {
+            technique: "X-Wing",
+            placements: Vec::new(),
+            eliminations: vec![((3, 3), 2)],
+            reasons: Vec::new(),
+        };
+        elimination.apply(&layout, &mut puzzle, &mut notes);
+        assert_eq!(notes[3][3].bits(), 0b000_000_001);
+    }
+
+    #[test]
+    fn test_display() {
+        let placement = Deduction::placement("Hidden Single", (2, 3), 7);
+        assert_eq!(placement.to_string(), "Hidden Single places 7 at r3c4.");
+        let elimination = Deduction {
+            technique: "Naked Pair",
+            placements: Vec::new(),
+            eliminations: vec![((0, 6), 3), ((0, 7), 3), ((1, 1), 5)],
+            reasons: vec![(0, 0), (0, 1)],
+        };
+        assert_eq!(
+            elimination.to_string(),
+            "Naked Pair on r1c1, r1c2 removes 3 from r1c7, r1c8 and 5 from r2c2."
+        );
+    }
+}
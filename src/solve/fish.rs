@@ -0,0 +1,417 @@
+use crate::solve::notes::*;
+use crate::solve::coordinates::*;
+use crate::solve::map_notes::map;
+use crate::solve::candidate_set::CandidateSet;
+use crate::solve::deduction::Deduction;
+
+pub const FISH_NAMES: [[&str; 3]; 3] = [
+    ["X-Wing", "Swordfish", "Jellyfish"],
+    ["Finned X-Wing", "Finned Swordfish", "Finned Jellyfish"],
+    ["Sashimi X-Wing", "Sashimi Swordfish", "Sashimi Jellyfish"],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Orientation {
+    Row,
+    Col,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FishKind {
+    Basic,
+    Finned,
+    Sashimi,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Fish {
+    pub value: usize,
+    pub orientation: Orientation,
+    pub kind: FishKind,
+    pub base: Vec<usize>,
+    pub cover: Vec<usize>,
+    pub fins: Vec<Point>,
+    pub eliminations: Vec<Point>,
+}
+
+impl Orientation {
+    fn base_line<const N: usize>(&self, layout: &Layout<N>, index: usize) -> Coordinates<N> {
+        match self {
+            Orientation::Row => layout.get_row_coordinates(index),
+            Orientation::Col => layout.get_col_coordinates(index),
+        }
+    }
+
+    fn cover_line<const N: usize>(&self, layout: &Layout<N>, index: usize) -> Coordinates<N> {
+        match self {
+            Orientation::Row => layout.get_col_coordinates(index),
+            Orientation::Col => layout.get_row_coordinates(index),
+        }
+    }
+
+    fn get_fin_span<const N: usize>(&self, layout: &Layout<N>) -> usize {
+        (0..N)
+            .map(|region| {
+                let cover_lines: CandidateSet = layout
+                    .get_region_coordinates(region)
+                    .iter()
+                    .map(|(row, col)| match self {
+                        Orientation::Row => col + 1,
+                        Orientation::Col => row + 1,
+                    })
+                    .collect();
+                cover_lines.count()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl Fish {
+    pub fn clear<const N: usize>(&self, notes: &mut Notes<N>) -> bool {
+        clear_value(notes, self.value, &self.eliminations)
+    }
+
+    pub fn name(&self) -> &'static str {
+        FISH_NAMES[self.kind as usize][self.base.len() - 2]
+    }
+
+    pub fn apply<const N: usize>(
+        &self,
+        layout: &Layout<N>,
+        notes: &mut Notes<N>
+    ) -> Option<Deduction> {
+        let reasons: Vec<Point> = self.base
+            .iter()
+            .flat_map(|line| self.orientation.base_line(layout, *line))
+            .filter(|(row, col)| notes[*row][*col].contains(self.value))
+            .collect();
+        Deduction::record(self.name(), &reasons, notes, |notes| self.clear(notes))
+    }
+}
+
+pub fn use_basic_fish<const N: usize>(
+    layout: &Layout<N>,
+    notes: &mut Notes<N>,
+    size: usize
+) -> Vec<Deduction> {
+    find_fish(layout, notes, size).iter().filter_map(|fish| fish.apply(layout, notes)).collect()
+}
+
+pub fn use_finned_fish<const N: usize>(
+    layout: &Layout<N>,
+    notes: &mut Notes<N>,
+    size: usize
+) -> Vec<Deduction> {
+    find_finned_fish(layout, notes, size)
+        .iter()
+        .filter_map(|fish| fish.apply(layout, notes))
+        .collect()
+}
+
+pub fn find_fish<const N: usize>(layout: &Layout<N>, notes: &Notes<N>, size: usize) -> Vec<Fish> {
+    let mut found = Vec::new();
+    for value in 1..=N {
+        for orientation in [Orientation::Row, Orientation::Col] {
+            found.extend(find_fish_on_lines(layout, notes, value, size, orientation));
+        }
+    }
+    found
+}
+
+pub fn find_finned_fish<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    size: usize
+) -> Vec<Fish> {
+    let mut found = Vec::new();
+    for value in 1..=N {
+        for orientation in [Orientation::Row, Orientation::Col] {
+            found.extend(find_finned_fish_on_lines(layout, notes, value, size, orientation));
+        }
+    }
+    found
+}
+
+fn find_fish_on_lines<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize,
+    size: usize,
+    orientation: Orientation
+) -> Vec<Fish> {
+    let positions = get_positions(layout, notes, value, orientation);
+    let lines: Vec<usize> = (0..N)
+        .filter(|line| (2..=size).contains(&positions[*line].count()))
+        .collect();
+    let mut found = Vec::new();
+    for base in combinations(&lines, size) {
+        let cover_lines = base
+            .iter()
+            .fold(CandidateSet::EMPTY, |cover, line| cover | positions[*line]);
+        if cover_lines.count() != size {
+            continue;
+        }
+        let cover = to_lines(cover_lines);
+        let eliminations = get_eliminations(layout, notes, value, orientation, &base, &cover, None);
+        if !eliminations.is_empty() {
+            let (kind, fins) = (FishKind::Basic, Vec::new());
+            found.push(Fish { value, orientation, kind, base, cover, fins, eliminations });
+        }
+    }
+    found
+}
+
+fn find_finned_fish_on_lines<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize,
+    size: usize,
+    orientation: Orientation
+) -> Vec<Fish> {
+    let positions = get_positions(layout, notes, value, orientation);
+    let limit = size + orientation.get_fin_span(layout);
+    let lines: Vec<usize> = (0..N)
+        .filter(|line| (2..=limit).contains(&positions[*line].count()))
+        .collect();
+    let mut found = Vec::new();
+    for base in combinations(&lines, size) {
+        let all_lines = base
+            .iter()
+            .fold(CandidateSet::EMPTY, |cover, line| cover | positions[*line]);
+        if !(size + 1..=limit).contains(&all_lines.count()) {
+            continue;
+        }
+        for cover in combinations(&to_lines(all_lines), size) {
+            let fish = check_fins(layout, notes, value, orientation, &positions, &base, cover);
+            found.extend(fish);
+        }
+    }
+    found
+}
+
+fn check_fins<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize,
+    orientation: Orientation,
+    positions: &[CandidateSet; N],
+    base: &[usize],
+    cover: Vec<usize>
+) -> Option<Fish> {
+    let cover_lines: CandidateSet = cover.iter().map(|line| line + 1).collect();
+    let mut kind = FishKind::Finned;
+    let mut fins = Vec::new();
+    for line in base {
+        match (positions[*line] & cover_lines).count() {
+            0 => {
+                return None;
+            }
+            1 => {
+                kind = FishKind::Sashimi;
+            }
+            _ => {}
+        }
+        let coordinates = orientation.base_line(layout, *line);
+        for position in to_lines(positions[*line] - cover_lines) {
+            fins.push(coordinates[position]);
+        }
+    }
+    let (row, col) = fins[0];
+    let region = layout.get_region_coordinates(layout.get_region((row, col)));
+    if !fins.iter().all(|fin| region.contains(fin)) {
+        return None;
+    }
+    let eliminations = get_eliminations(
+        layout,
+        notes,
+        value,
+        orientation,
+        base,
+        &cover,
+        Some(&region)
+    );
+    if eliminations.is_empty() {
+        return None;
+    }
+    Some(Fish { value, orientation, kind, base: base.to_vec(), cover, fins, eliminations })
+}
+
+fn get_positions<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize,
+    orientation: Orientation
+) -> [CandidateSet; N] {
+    let mut positions = [CandidateSet::EMPTY; N];
+    for (line, position) in positions.iter_mut().enumerate() {
+        *position = map(notes, &orientation.base_line(layout, line))[value - 1];
+    }
+    positions
+}
+
+fn get_eliminations<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize,
+    orientation: Orientation,
+    base: &[usize],
+    cover: &[usize],
+    fin_square: Option<&Coordinates<N>>
+) -> Vec<Point> {
+    let mut eliminations = Vec::new();
+    for line in cover {
+        for (index, (row, col)) in orientation.cover_line(layout, *line).iter().enumerate() {
+            let sees_fins = fin_square.is_none_or(|square| square.contains(&(*row, *col)));
+            if sees_fins && !base.contains(&index) && notes[*row][*col].contains(value) {
+                eliminations.push((*row, *col));
+            }
+        }
+    }
+    eliminations
+}
+
+fn to_lines(positions: CandidateSet) -> Vec<usize> {
+    positions.iter().map(|position| position - 1).collect()
+}
+
+pub fn combinations(items: &[usize], size: usize) -> Vec<Vec<usize>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    let mut output = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        for mut rest in combinations(&items[index + 1..], size - 1) {
+            rest.insert(0, *item);
+            output.push(rest);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::grid_size::GRID_SIZE;
+
+    fn notes_with(value: usize, cells: &[Point]) -> Notes {
+        let mut notes: Notes = [[CandidateSet::EMPTY; GRID_SIZE]; GRID_SIZE];
+        for (row, col) in cells {
+            notes[*row][*col].insert(value);
+        }
+        notes
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(combinations(&[1, 2, 3], 2), vec![vec![1, 2], vec![1, 3], vec![2, 3]]);
+        assert_eq!(combinations(&[1, 2], 3), Vec::<Vec<usize>>::new());
+    }
+
+    #[test]
+    fn test_row_x_wing() {
+        let layout = Layout::default();
+        let mut notes = notes_with(
+            1,
+            &[(1, 2), (1, 6), (5, 2), (5, 6), (3, 2), (3, 4), (7, 6), (7, 0)]
+        );
+        let fishes = find_fish(&layout, &notes, 2);
+        assert_eq!(fishes.len(), 1);
+        assert_eq!(fishes[0].orientation, Orientation::Row);
+        assert_eq!(fishes[0].base, vec![1, 5]);
+        assert_eq!(fishes[0].cover, vec![2, 6]);
+        assert_eq!(fishes[0].eliminations, vec![(3, 2), (7, 6)]);
+
+        let deductions = use_basic_fish(&layout, &mut notes, 2);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(
+            deductions[0].to_string(),
+            "X-Wing on r2c3, r2c7, r6c3, r6c7 removes 1 from r4c3, r8c7."
+        );
+        assert!(notes[3][2].is_empty());
+        assert!(notes[7][6].is_empty());
+        assert_eq!(notes[1][2], CandidateSet::from_value(1));
+        assert_eq!(notes[3][4], CandidateSet::from_value(1));
+        assert!(use_basic_fish(&layout, &mut notes, 2).is_empty());
+    }
+
+    #[test]
+    fn test_col_x_wing() {
+        let layout = Layout::default();
+        let mut notes = notes_with(9, &[(2, 0), (7, 0), (2, 4), (7, 4), (2, 8), (7, 1)]);
+        assert!(!use_basic_fish(&layout, &mut notes, 2).is_empty());
+        assert!(notes[2][8].is_empty());
+        assert!(notes[7][1].is_empty());
+        assert_eq!(notes[2][0], CandidateSet::from_value(9));
+        assert_eq!(notes[7][4], CandidateSet::from_value(9));
+    }
+
+    #[test]
+    fn test_swordfish() {
+        let layout = Layout::default();
+        let notes = notes_with(
+            4,
+            &[(0, 1), (0, 4), (3, 4), (3, 7), (6, 1), (6, 7), (8, 1), (8, 5), (4, 7), (4, 2)]
+        );
+        assert!(find_fish(&layout, &notes, 2).is_empty());
+        let fishes = find_fish(&layout, &notes, 3);
+        assert_eq!(fishes.len(), 1);
+        assert_eq!(fishes[0].base, vec![0, 3, 6]);
+        assert_eq!(fishes[0].cover, vec![1, 4, 7]);
+        assert_eq!(fishes[0].eliminations, vec![(8, 1), (4, 7)]);
+    }
+
+    #[test]
+    fn test_jellyfish() {
+        let layout = Layout::default();
+        let mut notes = notes_with(
+            7,
+            &[(0, 0), (1, 0), (1, 3), (3, 3), (3, 5), (5, 5), (5, 8), (0, 8), (8, 0), (8, 4)]
+        );
+        let fishes = find_fish(&layout, &notes, 4);
+        assert_eq!(fishes.len(), 1);
+        assert_eq!(fishes[0].orientation, Orientation::Row);
+        assert_eq!(fishes[0].base, vec![0, 1, 3, 5]);
+        assert_eq!(fishes[0].cover, vec![0, 3, 5, 8]);
+        assert_eq!(fishes[0].eliminations, vec![(8, 0)]);
+        assert!(!use_basic_fish(&layout, &mut notes, 4).is_empty());
+        assert!(notes[8][0].is_empty());
+        assert_eq!(notes[8][4], CandidateSet::from_value(7));
+    }
+
+    #[test]
+    fn test_finned_x_wing() {
+        let layout = Layout::default();
+        let mut notes = notes_with(
+            5,
+            &[(1, 1), (1, 6), (4, 1), (4, 6), (4, 8), (2, 1), (3, 6), (5, 6), (7, 6)]
+        );
+        assert!(find_fish(&layout, &notes, 2).is_empty());
+        let fishes = find_finned_fish(&layout, &notes, 2);
+        assert_eq!(fishes.len(), 1);
+        assert_eq!(fishes[0].kind, FishKind::Finned);
+        assert_eq!(fishes[0].base, vec![1, 4]);
+        assert_eq!(fishes[0].cover, vec![1, 6]);
+        assert_eq!(fishes[0].fins, vec![(4, 8)]);
+        assert_eq!(fishes[0].eliminations, vec![(3, 6), (5, 6)]);
+        assert!(!use_finned_fish(&layout, &mut notes, 2).is_empty());
+        assert!(notes[3][6].is_empty());
+        assert_eq!(notes[7][6], CandidateSet::from_value(5));
+    }
+
+    #[test]
+    fn test_sashimi_x_wing() {
+        let layout = Layout::default();
+        let notes = notes_with(
+            5,
+            &[(1, 1), (1, 6), (4, 1), (4, 7), (4, 8), (7, 1), (3, 6), (5, 6)]
+        );
+        let fishes = find_finned_fish(&layout, &notes, 2);
+        assert_eq!(fishes.len(), 1);
+        assert_eq!(fishes[0].kind, FishKind::Sashimi);
+        assert_eq!(fishes[0].orientation, Orientation::Row);
+        assert_eq!(fishes[0].cover, vec![1, 6]);
+        assert_eq!(fishes[0].fins, vec![(4, 7), (4, 8)]);
+        assert_eq!(fishes[0].eliminations, vec![(3, 6), (5, 6)]);
+    }
+}
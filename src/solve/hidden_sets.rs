@@ -1,52 +1,243 @@
-use crate::common::grid_size::GRID_SIZE;
+use std::ops::RangeInclusive;
 use crate::solve::notes::Notes;
 use crate::solve::coordinates::*;
+use crate::solve::map_notes::Counters;
+use crate::solve::candidate_set::CandidateSet;
+use crate::solve::fish::combinations;
+use crate::solve::deduction::Deduction;
 
-pub fn use_hidden_sets(notes: &mut Notes) -> bool {
-    perform_for_all_sets(|coordinates| { check_hidden_set(notes, coordinates) })
+pub const MAX_SET_SIZE: usize = 4;
+pub const NAKED_SETS: [&str; MAX_SET_SIZE - 1] = ["Naked Pair", "Naked Triple", "Naked Quad"];
+pub const HIDDEN_SETS: [&str; MAX_SET_SIZE - 1] = ["Hidden Pair", "Hidden Triple", "Hidden Quad"];
+
+pub fn use_naked_sets<const N: usize>(
+    layout: &Layout<N>,
+    notes: &mut Notes<N>,
+    sizes: RangeInclusive<usize>
+) -> Vec<Deduction> {
+    use_sets(layout, notes, check_naked_set, sizes)
 }
 
-fn check_hidden_set(notes: &mut Notes, cells: &Coordinates) -> bool {
-    let mut result = false;
-    let values_map = crate::solve::map_notes::map(notes, cells);
-    for first in 0..GRID_SIZE - 1 {
-        if values_map[first].count_ones() == 2 {
-            for second in first + 1..GRID_SIZE {
-                if values_map[second] == values_map[first] {
-                    result |= clear_hidden_set(notes, cells, candidate(first, second));
-                }
+pub fn use_hidden_sets<const N: usize>(
+    layout: &Layout<N>,
+    notes: &mut Notes<N>,
+    sizes: RangeInclusive<usize>
+) -> Vec<Deduction> {
+    use_sets(layout, notes, check_hidden_set, sizes)
+}
+
+fn use_sets<const N: usize, F>(
+    layout: &Layout<N>,
+    notes: &mut Notes<N>,
+    check: F,
+    sizes: RangeInclusive<usize>
+) -> Vec<Deduction>
+    where F: Fn(&mut Notes<N>, &Coordinates<N>, usize) -> Vec<Deduction>
+{
+    let mut deductions = Vec::new();
+    layout.perform_for_all_sets(|coordinates| {
+        for size in sizes.clone() {
+            let found = check(notes, coordinates, size);
+            if !found.is_empty() {
+                deductions.extend(found);
+                return true;
             }
         }
-        // TODO: check hidden triples
+        false
+    });
+    deductions
+}
+
+pub fn find_subsets<const N: usize>(
+    counters: &Counters<N>,
+    size: usize
+) -> Vec<(CandidateSet, CandidateSet)> {
+    let indexes: Vec<usize> = (0..N)
+        .filter(|index| !counters[*index].is_empty() && counters[*index].count() <= size)
+        .collect();
+    let mut found = Vec::new();
+    for subset in combinations(&indexes, size) {
+        let union = subset
+            .iter()
+            .fold(CandidateSet::EMPTY, |union, index| union | counters[*index]);
+        if union.count() == size {
+            let members = subset.iter().map(|index| index + 1).collect();
+            found.push((members, union));
+        }
     }
-    result
+    found
+}
+
+fn check_naked_set<const N: usize>(
+    notes: &mut Notes<N>,
+    cells: &Coordinates<N>,
+    size: usize
+) -> Vec<Deduction> {
+    let counters: Counters<N> = cells.map(|(row, col)| notes[row][col]);
+    let mut deductions = Vec::new();
+    for (members, values) in find_subsets(&counters, size) {
+        let reasons = get_cells(cells, members);
+        deductions.extend(Deduction::record(NAKED_SETS[size - 2], &reasons, notes, |notes| {
+            clear_naked_set(notes, cells, members, values)
+        }));
+    }
+    deductions
 }
 
-fn clear_hidden_set(notes: &mut Notes, cells: &Coordinates, candidate: u16) -> bool {
+pub fn clear_naked_set<const N: usize>(
+    notes: &mut Notes<N>,
+    cells: &Coordinates<N>,
+    members: CandidateSet,
+    values: CandidateSet
+) -> bool {
     let mut result = false;
-    for (row, col) in cells.iter() {
-        let note = notes[*row][*col];
-        let is_not_set = (note & candidate) != 0;
-        if is_not_set {
-            let is_not_candidate = (note | candidate) != candidate;
-            notes[*row][*col] &= candidate;
-            result |= is_not_candidate;
+    for (index, (row, col)) in cells.iter().enumerate() {
+        let note = &mut notes[*row][*col];
+        if !members.contains(index + 1) && !(*note & values).is_empty() {
+            *note -= values;
+            result = true;
         }
     }
     result
 }
 
-fn candidate(first: usize, second: usize) -> u16 {
-    (1 << second) | (1 << first)
+fn check_hidden_set<const N: usize>(
+    notes: &mut Notes<N>,
+    cells: &Coordinates<N>,
+    size: usize
+) -> Vec<Deduction> {
+    let mut deductions = Vec::new();
+    let values_map = crate::solve::map_notes::map(notes, cells);
+    for (values, positions) in find_subsets(&values_map, size) {
+        let reasons = get_cells(cells, positions);
+        deductions.extend(Deduction::record(HIDDEN_SETS[size - 2], &reasons, notes, |notes| {
+            clear_hidden_set(notes, cells, positions, values)
+        }));
+    }
+    deductions
+}
+
+fn get_cells<const N: usize>(cells: &Coordinates<N>, positions: CandidateSet) -> Vec<Point> {
+    positions.iter().map(|position| cells[position - 1]).collect()
+}
+
+fn clear_hidden_set<const N: usize>(
+    notes: &mut Notes<N>,
+    cells: &Coordinates<N>,
+    positions: CandidateSet,
+    values: CandidateSet
+) -> bool {
+    let mut result = false;
+    for (index, (row, col)) in cells.iter().enumerate() {
+        let note = &mut notes[*row][*col];
+        if positions.contains(index + 1) && !note.is_subset(values) {
+            *note &= values;
+            result = true;
+        }
+    }
+    result
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::grid_size::GRID_SIZE;
+
+    fn notes_on_row(row_notes: [u32; GRID_SIZE]) -> Notes {
+        let mut notes: Notes = Default::default();
+        notes[0] = row_notes.map(CandidateSet::from_bits);
+        notes
+    }
+
+    fn first_row() -> Coordinates {
+        Layout::default().get_row_coordinates(0)
+    }
+
+    fn get_bits(notes: &[CandidateSet]) -> Vec<u32> {
+        notes.iter().map(|note| note.bits()).collect()
+    }
+
+    #[test]
+    fn test_find_subsets() {
+        let counters = [0b011, 0b011, 0b111, 0, 0, 0, 0, 0, 0].map(CandidateSet::from_bits);
+        let (pair, triple) = (CandidateSet::from_bits(0b011), CandidateSet::from_bits(0b111));
+        assert_eq!(find_subsets(&counters, 2), vec![(pair, pair)]);
+        assert_eq!(find_subsets(&counters, 3), vec![(triple, triple)]);
+        assert!(find_subsets(&counters, 4).is_empty());
+    }
+
+    #[test]
+    fn test_naked_pair() {
+        let mut notes = notes_on_row([
+            0b000_000_011, 0b000_000_011, 0b000_000_111, 0b000_001_001, 0, 0, 0, 0, 0,
+        ]);
+        assert!(!use_naked_sets(&Layout::default(), &mut notes, 2..=MAX_SET_SIZE).is_empty());
+        assert_eq!(
+            get_bits(&notes[0][..4]),
+            [0b000_000_011, 0b000_000_011, 0b000_000_100, 0b000_001_000]
+        );
+    }
+
+    #[test]
+    fn test_naked_triple() {
+        let mut notes = notes_on_row([
+            0b000_000_011, 0b000_000_110, 0b000_000_101, 0b000_001_111, 0b000_011_001, 0, 0, 0, 0,
+        ]);
+        let deductions = check_naked_set(&mut notes, &first_row(), 3);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].technique, "Naked Triple");
+        assert_eq!(deductions[0].reasons, vec![(0, 0), (0, 1), (0, 2)]);
+        assert_eq!(notes[0][3].bits(), 0b000_001_000);
+        assert_eq!(notes[0][4].bits(), 0b000_011_000);
+    }
+
+    #[test]
+    fn test_naked_quad() {
+        let mut notes = notes_on_row([
+            0b000_000_011, 0b000_000_110, 0b000_001_100, 0b000_001_001, 0b000_111_111,
+            0b001_010_001, 0, 0, 0,
+        ]);
+        assert!(!check_naked_set(&mut notes, &first_row(), 4).is_empty());
+        assert_eq!(notes[0][4].bits(), 0b000_110_000);
+        assert_eq!(notes[0][5].bits(), 0b001_010_000);
+        assert_eq!(notes[0][0].bits(), 0b000_000_011);
+    }
+
+    #[test]
+    fn test_hidden_pair() {
+        let mut notes = notes_on_row([
+            0b000_110_011, 0b000_100_011, 0b000_110_100, 0b000_110_100, 0b000_110_100, 0, 0, 0, 0,
+        ]);
+        let deductions = check_hidden_set(&mut notes, &first_row(), 2);
+        assert_eq!(deductions[0].technique, "Hidden Pair");
+        assert_eq!(deductions[0].reasons, vec![(0, 0), (0, 1)]);
+        assert_eq!(deductions[0].eliminations, vec![((0, 0), 5), ((0, 0), 6), ((0, 1), 6)]);
+        assert_eq!(notes[0][0].bits(), 0b000_000_011);
+        assert_eq!(notes[0][1].bits(), 0b000_000_011);
+        assert_eq!(notes[0][2].bits(), 0b000_110_100);
+    }
+
+    #[test]
+    fn test_hidden_triple() {
+        let mut notes = notes_on_row([
+            0b000_110_011, 0b000_100_110, 0b000_010_101, 0b000_111_000, 0b000_111_000, 0, 0, 0, 0,
+        ]);
+        assert!(!check_hidden_set(&mut notes, &first_row(), 3).is_empty());
+        assert_eq!(get_bits(&notes[0][..3]), [0b000_000_011, 0b000_000_110, 0b000_000_101]);
+        assert_eq!(notes[0][3].bits(), 0b000_111_000);
+    }
 
     #[test]
-    fn test_check_candidate() {
-        assert_eq!(candidate(0, 1), 0b11);
-        assert_eq!(candidate(8, 6), 0b101_000_000);
+    fn test_hidden_quad() {
+        let mut notes = notes_on_row([
+            0b011_000_011, 0b011_000_110, 0b010_001_100, 0b101_001_001, 0b111_110_000,
+            0b111_110_000, 0b111_110_000, 0, 0,
+        ]);
+        assert!(!check_hidden_set(&mut notes, &first_row(), 4).is_empty());
+        assert_eq!(
+            get_bits(&notes[0][..4]),
+            [0b000_000_011, 0b000_000_110, 0b000_001_100, 0b000_001_001]
+        );
+        assert_eq!(notes[0][4].bits(), 0b111_110_000);
     }
 }
@@ -0,0 +1,171 @@
+use crate::common::puzzle::Puzzle;
+use crate::solve::notes::*;
+use crate::solve::coordinates::*;
+use crate::solve::deduction::*;
+use crate::solve::validation::*;
+use crate::solve::candidate_set::CandidateSet;
+use crate::solve::technique::Pipeline;
+
+pub fn hint<const N: usize>(
+    layout: &Layout<N>,
+    puzzle: &Puzzle<N>,
+    notes: &Notes<N>,
+    uniqueness: bool
+) -> Result<Option<Deduction>, SolverError> {
+    validate_puzzle(layout, puzzle)?;
+    let mut notes = *notes;
+    for (row, puzzle_row) in puzzle.iter().enumerate() {
+        for (col, value) in puzzle_row.iter().enumerate() {
+            if *value != 0 {
+                notes[row][col] = CandidateSet::EMPTY;
+            }
+        }
+    }
+    validate_notes(layout, puzzle, &notes)?;
+    if let Some(solved_peers) = find_solved_peers(layout, puzzle, &notes) {
+        return Ok(Some(solved_peers));
+    }
+    let pipeline: Pipeline<N> = Pipeline::default();
+    let layout = &layout.without_solved(puzzle);
+    let deduction = pipeline
+        .iter()
+        .filter(|technique| uniqueness || !technique.assumes_uniqueness())
+        .find_map(|technique| {
+            let mut copy = notes;
+            technique.apply(layout, &mut copy).into_iter().next()
+        });
+    Ok(deduction)
+}
+
+fn find_solved_peers<const N: usize>(
+    layout: &Layout<N>,
+    puzzle: &Puzzle<N>,
+    notes: &Notes<N>
+) -> Option<Deduction> {
+    for (row, puzzle_row) in puzzle.iter().enumerate() {
+        for (col, value) in puzzle_row.iter().enumerate() {
+            if *value == 0 {
+                continue;
+            }
+            let mut cleared = *notes;
+            let found = Deduction::record(SOLVED_PEERS, &[(row, col)], &mut cleared, |notes| {
+                clear_placed(layout, notes, (row, col), *value as usize)
+            });
+            if found.is_some() {
+                return found;
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::grid_size::GRID_SIZE;
+    use crate::solve::notes_manager::NotesManager;
+
+    const PUZZLE: Puzzle = [
+        [5, 3, 0, 0, 7, 0, 0, 0, 0],
+        [6, 0, 0, 1, 9, 5, 0, 0, 0],
+        [0, 9, 8, 0, 0, 0, 0, 6, 0],
+        [8, 0, 0, 0, 6, 0, 0, 0, 3],
+        [4, 0, 0, 8, 0, 3, 0, 0, 1],
+        [7, 0, 0, 0, 2, 0, 0, 0, 6],
+        [0, 6, 0, 0, 0, 0, 2, 8, 0],
+        [0, 0, 0, 4, 1, 9, 0, 0, 5],
+        [0, 0, 0, 0, 8, 0, 0, 7, 9],
+    ];
+
+    const HARD_PUZZLE: Puzzle = [
+        [3, 1, 0, 0, 0, 0, 2, 0, 0],
+        [0, 0, 0, 0, 0, 0, 1, 0, 9],
+        [0, 0, 8, 0, 6, 2, 0, 0, 3],
+        [0, 8, 0, 4, 7, 0, 0, 0, 0],
+        [0, 0, 7, 0, 0, 0, 6, 0, 0],
+        [0, 0, 0, 0, 8, 1, 0, 9, 0],
+        [7, 0, 0, 8, 3, 0, 9, 0, 0],
+        [8, 0, 5, 0, 0, 0, 0, 0, 0],
+        [0, 0, 6, 0, 0, 0, 0, 4, 7],
+    ];
+
+    fn get_notes(puzzle: &Puzzle) -> Notes {
+        let mut notes_manager = NotesManager::new(*puzzle);
+        notes_manager.fill().unwrap();
+        notes_manager.get()
+    }
+
+    #[test]
+    fn test_simplest_first() {
+        let layout = Layout::default();
+        let hint = hint(&layout, &PUZZLE, &get_notes(&PUZZLE), true).unwrap();
+        assert_eq!(hint, Some(Deduction::placement(NAKED_SINGLE, (4, 4), 5)));
+    }
+
+    #[test]
+    fn test_naked_single() {
+        let layout = Layout::default();
+        let empty = [[0; GRID_SIZE]; GRID_SIZE];
+        let mut notes = get_notes(&empty);
+        notes[3][3] = CandidateSet::from_value(7);
+        let hint = hint(&layout, &empty, &notes, true).unwrap();
+        assert_eq!(hint, Some(Deduction::placement(NAKED_SINGLE, (3, 3), 7)));
+    }
+
+    #[test]
+    fn test_user_notes() {
+        let layout = Layout::default();
+        let mut notes = get_notes(&PUZZLE);
+        notes[0][2] = CandidateSet::from_value(4);
+        let hint = hint(&layout, &PUZZLE, &notes, true).unwrap();
+        assert_eq!(hint, Some(Deduction::placement(NAKED_SINGLE, (0, 2), 4)));
+    }
+
+    #[test]
+    fn test_solved_peers() {
+        let layout = Layout::default();
+        let mut notes = get_notes(&PUZZLE);
+        notes[0][2].insert(5);
+        notes[0][3].insert(5);
+        let expected = Deduction {
+            technique: SOLVED_PEERS,
+            placements: Vec::new(),
+            eliminations: vec![((0, 2), 5), ((0, 3), 5)],
+            reasons: vec![(0, 0)],
+        };
+        assert_eq!(hint(&layout, &PUZZLE, &notes, true).unwrap(), Some(expected));
+    }
+
+    #[test]
+    fn test_hidden_single() {
+        let layout = Layout::default();
+        let empty = [[0; GRID_SIZE]; GRID_SIZE];
+        let mut notes = get_notes(&empty);
+        for note in notes[0].iter_mut().skip(1) {
+            note.remove(1);
+        }
+        let hint = hint(&layout, &empty, &notes, true).unwrap();
+        assert_eq!(hint, Some(Deduction::placement(HIDDEN_SINGLE, (0, 0), 1)));
+    }
+
+    #[test]
+    fn test_invalid_notes() {
+        let layout = Layout::default();
+        let mut notes = get_notes(&PUZZLE);
+        notes[0][2] = CandidateSet::EMPTY;
+        let error = SolverError::EmptyCandidates { cell: (0, 2) };
+        assert_eq!(hint(&layout, &PUZZLE, &notes, true), Err(error));
+    }
+
+    #[test]
+    fn test_hints_solve_puzzle() {
+        let layout = Layout::default();
+        let mut puzzle = HARD_PUZZLE;
+        let mut notes = get_notes(&puzzle);
+        while let Some(deduction) = hint(&layout, &puzzle, &notes, true).unwrap() {
+            deduction.apply(&layout, &mut puzzle, &mut notes);
+        }
+        assert!(puzzle.iter().all(|row| !row.contains(&0)));
+        assert_eq!(Some(puzzle), crate::solve::dancing_links::solve(&layout, &HARD_PUZZLE));
+    }
+}
@@ -0,0 +1,210 @@
+use serde::Serialize;
+use serde::Deserialize;
+use crate::common::puzzle::Puzzle;
+use crate::solve::notes::Notes;
+use crate::solve::coordinates::*;
+use crate::solve::candidate_set::CandidateSet;
+use crate::solve::deduction::Deduction;
+
+const THERMOMETER: &str = "Thermometer";
+const ARROW: &str = "Arrow";
+const PALINDROME: &str = "Palindrome";
+const GERMAN_WHISPERS: &str = "German Whispers";
+const WHISPER_GAP: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LineKind {
+    Thermometer,
+    Arrow,
+    Palindrome,
+    Whispers,
+}
+
+impl LineKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            LineKind::Thermometer => THERMOMETER,
+            LineKind::Arrow => ARROW,
+            LineKind::Palindrome => PALINDROME,
+            LineKind::Whispers => GERMAN_WHISPERS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Line {
+    pub kind: LineKind,
+    pub cells: Vec<Point>,
+}
+
+impl Line {
+    #[cfg(test)]
+    pub fn new(kind: LineKind, cells: Vec<Point>) -> Self {
+        Line { kind, cells }
+    }
+
+    pub fn fits<const N: usize>(
+        &self,
+        layout: &Layout<N>,
+        puzzle: &Puzzle<N>,
+        notes: &Notes<N>
+    ) -> bool {
+        let mut candidates: Vec<CandidateSet> = self.cells
+            .iter()
+            .map(|(row, col)| match puzzle[*row][*col] {
+                0 => notes[*row][*col],
+                value => CandidateSet::from_value(value as usize),
+            })
+            .collect();
+        self.prune(layout, &mut candidates);
+        candidates.iter().all(|values| !values.is_empty())
+    }
+
+    pub fn restrict<const N: usize>(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> bool {
+        let mut candidates: Vec<CandidateSet> = self.cells
+            .iter()
+            .map(|(row, col)| match layout.get_solved((*row, *col)) {
+                0 => notes[*row][*col],
+                value => CandidateSet::from_value(value as usize),
+            })
+            .collect();
+        self.prune(layout, &mut candidates);
+        let mut result = false;
+        for ((row, col), values) in self.cells.iter().zip(candidates) {
+            if layout.get_solved((*row, *col)) != 0 {
+                continue;
+            }
+            let note = &mut notes[*row][*col];
+            result |= !note.is_subset(values);
+            *note &= values;
+        }
+        result
+    }
+
+    fn prune<const N: usize>(&self, layout: &Layout<N>, candidates: &mut [CandidateSet]) {
+        match self.kind {
+            LineKind::Thermometer => prune_thermometer(candidates),
+            LineKind::Arrow => prune_arrow(layout, &self.cells, candidates),
+            LineKind::Palindrome => prune_palindrome(candidates),
+            LineKind::Whispers => prune_whispers(candidates),
+        }
+    }
+}
+
+pub fn use_lines<const N: usize>(layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+    let mut deductions = Vec::new();
+    for line in layout.get_lines() {
+        deductions.extend(Deduction::record(line.kind.name(), &line.cells, notes, |notes| {
+            line.restrict(layout, notes)
+        }));
+    }
+    deductions
+}
+
+fn prune_thermometer(candidates: &mut [CandidateSet]) {
+    let mut low = 0;
+    for values in candidates.iter_mut() {
+        *values = values.iter().filter(|value| *value > low).collect();
+        low = values.min().unwrap_or(usize::MAX);
+    }
+    let mut high = usize::MAX;
+    for values in candidates.iter_mut().rev() {
+        *values = values.iter().filter(|value| *value < high).collect();
+        high = values.max().unwrap_or(0);
+    }
+}
+
+fn prune_palindrome(candidates: &mut [CandidateSet]) {
+    let count = candidates.len();
+    for index in 0..count / 2 {
+        let values = candidates[index] & candidates[count - 1 - index];
+        candidates[index] = values;
+        candidates[count - 1 - index] = values;
+    }
+}
+
+fn prune_whispers(candidates: &mut [CandidateSet]) {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for index in 0..candidates.len().saturating_sub(1) {
+            let (first, second) = (candidates[index], candidates[index + 1]);
+            candidates[index] = get_whisper_partners(first, second);
+            candidates[index + 1] = get_whisper_partners(second, first);
+            changed |= candidates[index] != first || candidates[index + 1] != second;
+        }
+    }
+}
+
+fn get_whisper_partners(values: CandidateSet, others: CandidateSet) -> CandidateSet {
+    let (Some(low), Some(high)) = (others.min(), others.max()) else {
+        return CandidateSet::EMPTY;
+    };
+    values
+        .iter()
+        .filter(|value| *value >= low + WHISPER_GAP || *value + WHISPER_GAP <= high)
+        .collect()
+}
+
+fn prune_arrow<const N: usize>(
+    layout: &Layout<N>,
+    cells: &[Point],
+    candidates: &mut [CandidateSet]
+) {
+    let Some((circle, shaft)) = cells.split_first() else {
+        return;
+    };
+    let Some((totals, values)) = candidates.split_first_mut() else {
+        return;
+    };
+    let mut possible = vec![CandidateSet::EMPTY; shaft.len()];
+    let mut reached = CandidateSet::EMPTY;
+    let circle = (*circle, *totals);
+    collect_sums(layout, circle, shaft, values, &mut Vec::new(), &mut reached, &mut possible);
+    *totals = reached;
+    values.copy_from_slice(&possible);
+}
+
+fn collect_sums<const N: usize>(
+    layout: &Layout<N>,
+    (circle, totals): (Point, CandidateSet),
+    cells: &[Point],
+    candidates: &[CandidateSet],
+    assigned: &mut Vec<usize>,
+    reached: &mut CandidateSet,
+    possible: &mut [CandidateSet]
+) {
+    let total: usize = assigned.iter().sum();
+    let Some(values) = candidates.get(assigned.len()) else {
+        let sees_total = cells
+            .iter()
+            .zip(assigned.iter())
+            .any(|(cell, value)| *value == total && layout.sees(*cell, circle));
+        if totals.contains(total) && !sees_total {
+            reached.insert(total);
+            for (values, value) in possible.iter_mut().zip(assigned.iter()) {
+                values.insert(*value);
+            }
+        }
+        return;
+    };
+    let cell = cells[assigned.len()];
+    let limit = totals.max().unwrap_or(0);
+    for value in values.iter().take_while(|value| total + value <= limit) {
+        let is_repeated = assigned
+            .iter()
+            .zip(cells)
+            .any(|(other, other_cell)| *other == value && layout.sees(*other_cell, cell));
+        if is_repeated {
+            continue;
+        }
+        assigned.push(value);
+        collect_sums(layout, (circle, totals), cells, candidates, assigned, reached, possible);
+        assigned.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    include!("ut/test_lines.rs");
+}
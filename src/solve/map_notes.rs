@@ -1,63 +1,65 @@
 use crate::common::grid_size::GRID_SIZE;
 use crate::solve::notes::Notes;
 use crate::solve::coordinates::*;
+use crate::solve::candidate_set::CandidateSet;
 
-pub type Counters = [u16; GRID_SIZE];
+pub type Counters<const N: usize = GRID_SIZE> = [CandidateSet; N];
 
-pub fn map(notes: &Notes, cells: &Coordinates) -> Counters {
-    let mut counters: Counters = Default::default();
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum House {
+    Row,
+    Col,
+    Region,
+    Extra,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrongLink {
+    pub house: House,
+    pub ends: [Point; 2],
+}
+
+pub fn map<const N: usize>(notes: &Notes<N>, cells: &Coordinates<N>) -> Counters<N> {
+    let mut counters: Counters<N> = [CandidateSet::EMPTY; N];
     for (index, (row, col)) in cells.iter().enumerate() {
-        let note = notes[*row][*col];
-        for value in 1..=GRID_SIZE {
-            if is_set(note, value) {
-                set_note(&mut counters[value - 1], index as u8);
-            }
+        for value in notes[*row][*col].iter() {
+            counters[value - 1].insert(index + 1);
         }
     }
     counters
 }
 
-// TODO: remove copied code -> extract Bitset class
-fn is_set(note: u16, position: usize) -> bool {
-    if is_valid_position(position) {
-        let mask = 1 << (position - 1);
-        (note & mask) != 0
-    } else {
-        false
+pub fn get_strong_links<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize
+) -> Vec<StrongLink> {
+    let mut links = Vec::new();
+    let extra_houses = layout
+        .get_extra_houses()
+        .iter()
+        .map(|coordinates| (House::Extra, *coordinates));
+    let houses = (0..N).flat_map(|index| layout.get_houses(index)).chain(extra_houses);
+    for (house, coordinates) in houses {
+        let ends: Vec<Point> = map(notes, &coordinates)[value - 1]
+            .iter()
+            .map(|position| coordinates[position - 1])
+            .collect();
+        if let [first, second] = ends[..] {
+            links.push(StrongLink { house, ends: [first, second] });
+        }
     }
-}
-
-fn set_note(note: &mut u16, bit: u8) {
-    *note |= 1 << bit;
-}
-
-fn is_valid_position(position: usize) -> bool {
-    position > 0 && position <= GRID_SIZE
+    links
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn test_is_set_basic() {
-        for i in 1..=GRID_SIZE {
-            assert!(!is_set(0, i));
-            assert!(is_set(0b111_111_111, i));
-        }
-    }
-
-    #[test]
-    fn test_is_set() {
-        let bitset = 0b111_000_111;
-        assert!(is_set(bitset, 1));
-        assert!(is_set(bitset, 8));
-        assert!(!is_set(bitset, 4));
-    }
+    use crate::solve::notes::from_bits;
 
     #[test]
     fn test_count() {
-        let notes: Notes = [
+        let notes = from_bits(&[
             [
                 0, 0, 0b000_001_011, 0b000_100_010, 0, 0b010_101_010, 0b110_001_001, 0b100_001_011,
                 0b010_001_010,
@@ -73,14 +75,26 @@ mod tests {
             [0, 0, 0b101_011_101, 0b001_010_100, 0b000_010_100, 0b001_000_000, 0, 0, 0b000_001_000],
             [0b000_000_110, 0b011_000_010, 0b001_000_110, 0, 0, 0, 0b000_100_100, 0b000_000_100, 0],
             [0, 0, 0b000_011_111, 0b000_110_110, 0, 0b000_100_010, 0, 0, 0],
-        ];
+        ]);
 
-        let coordinates = get_row_coordinates(2);
+        let coordinates = Layout::default().get_row_coordinates(2);
         let actual = map(&notes, &coordinates);
         let expected = [
             0b001_000_001, 0b100_101_001, 0b001_011_000, 0b101_110_000, 0b001_000_000, 0,
             0b101_000_000, 0, 0,
         ];
-        assert_eq!(actual, expected);
+        assert_eq!(actual, expected.map(CandidateSet::from_bits));
+    }
+
+    #[test]
+    fn test_strong_links() {
+        let mut notes: Notes = Default::default();
+        for (row, col) in [(0, 0), (0, 5), (4, 5), (6, 5)] {
+            notes[row][col] = CandidateSet::from_value(5);
+        }
+        let layout = Layout::default();
+        let links = get_strong_links(&layout, &notes, 5);
+        assert_eq!(links, vec![StrongLink { house: House::Row, ends: [(0, 0), (0, 5)] }]);
+        assert!(get_strong_links(&layout, &notes, 4).is_empty());
     }
 }
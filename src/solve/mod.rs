@@ -1,7 +1,25 @@
 pub mod solver;
 pub mod notes_manager;
-mod notes;
+pub mod dancing_links;
+pub mod solution_count;
+pub mod validation;
+pub mod deduction;
+pub mod hint;
+pub mod rating;
+pub mod notes;
+pub mod candidate_set;
+pub mod technique;
+pub mod cages;
+pub mod lines;
+mod singles;
 mod pointing_sets;
+mod fish;
+mod wings;
+mod short_chains;
+mod colouring;
+mod chains;
+mod uniqueness;
+mod als;
 mod hidden_sets;
-mod coordinates;
+pub mod coordinates;
 mod map_notes;
@@ -1,3 +1,41 @@
 use crate::common::grid_size::GRID_SIZE;
+use crate::solve::coordinates::*;
+use crate::solve::candidate_set::CandidateSet;
 
-pub type Notes = [[u16; GRID_SIZE]; GRID_SIZE];
+pub type Notes<const N: usize = GRID_SIZE> = [[CandidateSet; N]; N];
+
+pub fn clear_value<const N: usize>(notes: &mut Notes<N>, value: usize, cells: &[Point]) -> bool {
+    let mut result = false;
+    for (row, col) in cells.iter() {
+        result |= notes[*row][*col].remove(value);
+    }
+    result
+}
+
+pub fn clear_consecutive<const N: usize>(
+    notes: &mut Notes<N>,
+    value: usize,
+    cells: &[Point]
+) -> bool {
+    let mut result = false;
+    for (row, col) in cells.iter() {
+        result |= notes[*row][*col].remove(value - 1);
+        result |= notes[*row][*col].remove(value + 1);
+    }
+    result
+}
+
+pub fn clear_placed<const N: usize>(
+    layout: &Layout<N>,
+    notes: &mut Notes<N>,
+    cell: Point,
+    value: usize
+) -> bool {
+    let cleared = clear_value(notes, value, &layout.get_peers(cell));
+    clear_consecutive(notes, value, &layout.get_neighbours(cell)) || cleared
+}
+
+#[cfg(test)]
+pub fn from_bits<const N: usize>(bits: &[[u32; N]; N]) -> Notes<N> {
+    bits.map(|row| row.map(CandidateSet::from_bits))
+}
@@ -1,42 +1,71 @@
 use crate::common::grid_size::GRID_SIZE;
 use crate::common::puzzle::Puzzle;
 use crate::solve::coordinates::*;
-use crate::solve::notes::Notes;
-
-#[allow(clippy::unusual_byte_groupings)]
-const FILLED_BITSET: u16 = 0b111_111_111; // Group by 3 fits better to the project purpose
-
-pub struct NotesManager {
-    puzzle: Puzzle,
-    notes: Notes,
+use crate::solve::notes::*;
+use crate::solve::candidate_set::CandidateSet;
+use crate::solve::validation::*;
+use crate::solve::deduction::Deduction;
+use crate::solve::technique::Technique;
+
+pub struct NotesManager<const N: usize = GRID_SIZE> {
+    puzzle: Puzzle<N>,
+    notes: Notes<N>,
+    layout: Layout<N>,
 }
 
-impl NotesManager {
-    pub fn new(play_board: Puzzle) -> Self {
-        let notes: Notes = Default::default();
+impl<const N: usize> NotesManager<N> {
+    #[cfg(test)]
+    pub fn new(play_board: Puzzle<N>) -> Self {
+        Self::with_layout(Layout::default(), play_board)
+    }
+
+    pub fn with_layout(layout: Layout<N>, play_board: Puzzle<N>) -> Self {
         NotesManager {
             puzzle: play_board,
-            notes,
+            notes: [[CandidateSet::EMPTY; N]; N],
+            layout,
         }
     }
 
-    pub fn fill(&mut self) {
+    pub fn fill(&mut self) -> Result<(), SolverError> {
+        validate_puzzle(&self.layout, &self.puzzle)?;
         for (row, notes_row) in self.notes.iter_mut().enumerate() {
             for (col, note) in notes_row.iter_mut().enumerate() {
-                *note = if self.puzzle[row][col] != 0 { 0 } else { FILLED_BITSET };
+                *note = match self.puzzle[row][col] {
+                    0 => CandidateSet::full(N),
+                    _ => CandidateSet::EMPTY,
+                };
             }
         }
-        perform_for_all_sets(|coordinates| {
+        let layout = self.layout.clone();
+        layout.perform_for_all_sets(|coordinates| {
             self.set_notes(coordinates);
             false
         });
+        for cage in layout.get_cages() {
+            self.set_notes(&cage.cells);
+        }
+        for row in 0..N {
+            for col in 0..N {
+                let value = self.puzzle[row][col] as usize;
+                if value != 0 {
+                    clear_value(&mut self.notes, value, &layout.get_moves((row, col)));
+                    clear_consecutive(&mut self.notes, value, &layout.get_neighbours((row, col)));
+                }
+            }
+        }
+        validate_notes(&self.layout, &self.puzzle, &self.notes)
     }
 
-    pub fn get(&self) -> Notes {
+    pub fn get(&self) -> Notes<N> {
         self.notes
     }
 
-    fn set_notes(&mut self, coordinates: &Coordinates) {
+    pub fn get_layout(&self) -> &Layout<N> {
+        &self.layout
+    }
+
+    fn set_notes(&mut self, coordinates: &[Point]) {
         let note = self.get_notes(coordinates);
         for (row, col) in coordinates.iter() {
             if self.puzzle[*row][*col] == 0 {
@@ -45,138 +74,23 @@ impl NotesManager {
         }
     }
 
-    fn get_notes(&self, coordinates: &Coordinates) -> u16 {
-        let mut note: u16 = FILLED_BITSET;
+    fn get_notes(&self, coordinates: &[Point]) -> CandidateSet {
+        let mut note = CandidateSet::full(N);
         for (row, col) in coordinates.iter() {
-            let cell = self.puzzle[*row][*col];
-            if cell != 0 {
-                Self::unset_note(&mut note, cell);
-            }
+            note.remove(self.puzzle[*row][*col] as usize);
         }
         note
     }
 
-    pub fn adjust(&mut self, row: usize, col: usize, value: u8) {
-        for cell in self.notes[row].iter_mut() {
-            Self::unset_note(cell, value);
-        }
-
-        for row_pos in self.notes.iter_mut() {
-            Self::unset_note(&mut row_pos[col], value);
-        }
-
-        for (x, y) in get_square_coordinates((row / 3, col / 3)) {
-            Self::unset_note(&mut self.notes[x][y], value);
-        }
-        self.notes[row][col] = 0;
-    }
-
-    //TODO: extract bitset struct
-    fn unset_note(note: &mut u16, bit: u8) {
-        if Self::is_valid_position(bit as usize) {
-            *note &= !(1 << (bit - 1));
-        }
-    }
-
-    fn is_set(note: u16, position: usize) -> bool {
-        if Self::is_valid_position(position) {
-            let mask = 1 << (position - 1);
-            (note & mask) != 0
-        } else {
-            false
-        }
-    }
-
-    fn is_valid_position(position: usize) -> bool {
-        position > 0 && position <= GRID_SIZE
-    }
-
-    pub fn set_obvious_pairs(&mut self) -> bool {
-        perform_for_all_sets(|coordinates| { self.perform_obvious_set(coordinates) })
-    }
-
-    fn perform_obvious_set(&mut self, coordinates: &Coordinates) -> bool {
-        let mut result = false;
-        for (row, col) in coordinates.iter() {
-            if self.notes[*row][*col].count_ones() == 2 {
-                result |= self.handle_coresponding_note(coordinates, (*row, *col));
-            }
-        }
-        if result {
-            return true;
-        }
-        for (index, (row, col)) in coordinates.iter().enumerate() {
-            let note = self.notes[*row][*col];
-            if note.count_ones() <= 3 && note != 0 {
-                result |= self.handle_triple(coordinates, index, note);
-            }
-        }
-        result
-    }
-
-    fn handle_triple(&mut self, coordinates: &Coordinates, index: usize, first_note: u16) -> bool {
-        if index >= GRID_SIZE - 2 {
-            return false; // can't find triple on last or previous one
-        }
-        let mut result = false;
-        for second in index + 1..GRID_SIZE - 1 {
-            let (row, col) = coordinates[second];
-            let second_note = self.notes[row][col] | first_note;
-            if second_note.count_ones() <= 3 && self.notes[row][col] != 0 {
-                for third in second + 1..GRID_SIZE {
-                    let (row3, col3) = coordinates[third];
-                    let note = second_note | self.notes[row3][col3];
-                    if note.count_ones() <= 3 && self.notes[row3][col3] != 0 {
-                        result |= self.clear_notes_obvious_set_based(coordinates, note);
-                    }
-                }
-            }
-        }
-        result
-    }
-
-    fn handle_coresponding_note(&mut self, coordinates: &Coordinates, (x, y): Point) -> bool {
-        let mut any_progress = false;
-        let note = self.notes[x][y];
-        for (row, col) in coordinates.iter() {
-            if self.notes[*row][*col] == note && (*row, *col) != (x, y) {
-                any_progress |= self.clear_notes_obvious_set_based(coordinates, note);
-            }
-        }
-        any_progress
-    }
-
-    fn clear_notes_obvious_set_based(&mut self, coordinates: &Coordinates, pair_note: u16) -> bool {
-        let mut any_progress = false;
-        for (row, col) in coordinates.iter() {
-            let note = &mut self.notes[*row][*col];
-            let masked_note = *note & pair_note;
-            if masked_note != *note && masked_note != 0 {
-                any_progress = true;
-                *note &= !pair_note;
-            }
-        }
-        any_progress
-    }
-
-    pub fn get_hidden(&self, coordinates: &Coordinates, value: usize) -> Option<Point> {
-        let mut found = false;
-        let mut coordinate_found = None;
-        for (row, col) in coordinates.iter() {
-            if Self::is_set(self.notes[*row][*col], value) {
-                if found {
-                    return None;
-                }
-                found = true;
-                coordinate_found = Some((*row, *col));
+    pub fn use_technique(&mut self, technique: &dyn Technique<N>) -> Vec<Deduction> {
+        let layout = self.layout.without_solved(&self.puzzle);
+        let deductions = technique.apply(&layout, &mut self.notes);
+        for deduction in deductions.iter() {
+            for ((row, col), value) in deduction.placements.iter() {
+                self.puzzle[*row][*col] = *value;
             }
         }
-        coordinate_found
-    }
-
-    pub fn use_square_methods(&mut self) -> bool {
-        crate::solve::pointing_sets::Handler::new(&mut self.notes).handle() ||
-            crate::solve::hidden_sets::use_hidden_sets(&mut self.notes)
+        deductions
     }
 }
 
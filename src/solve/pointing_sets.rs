@@ -1,144 +1,196 @@
-use crate::common::grid_size::GRID_SIZE;
 use crate::solve::notes::Notes;
+use crate::solve::candidate_set::CandidateSet;
+use crate::solve::coordinates::*;
+use crate::solve::deduction::Deduction;
+use crate::solve::map_notes::House;
 
-pub struct Handler<'a> {
-    notes: &'a mut Notes,
-    progress: bool,
+pub const POINTING_SET: &str = "Pointing Set";
+
+pub fn use_pointing_sets<const N: usize>(
+    layout: &Layout<N>,
+    notes: &mut Notes<N>
+) -> Vec<Deduction> {
+    Handler::new(layout, notes).handle()
+}
+
+pub struct Handler<'a, const N: usize> {
+    layout: &'a Layout<N>,
+    notes: &'a mut Notes<N>,
+    deductions: Vec<Deduction>,
 }
 
-impl<'a> Handler<'a> {
-    pub fn new(notes: &'a mut Notes) -> Self {
+impl<'a, const N: usize> Handler<'a, N> {
+    pub fn new(layout: &'a Layout<N>, notes: &'a mut Notes<N>) -> Self {
         Handler {
+            layout,
             notes,
-            progress: false,
-        }
-    }
-
-    pub fn handle(&mut self) -> bool {
-        let square_corners = [0, 3, 6];
-        for start_x in square_corners {
-            for start_y in square_corners {
-                self.handle_pointing_on_square_row(start_x, start_y);
-                self.handle_pointing_on_square_col(start_x, start_y);
-            }
+            deductions: Vec::new(),
         }
-        self.progress
     }
 
-    fn handle_pointing_on_square_row(&mut self, row: usize, col: usize) {
-        let notes = self.get_square_row_notes(row, col);
-        for (i, note) in Self::get_pointing_from_notes(notes).iter().enumerate() {
-            if *note != 0 {
-                self.use_pointing_set_on_row(*note, row + i, col);
-            }
+    pub fn handle(mut self) -> Vec<Deduction> {
+        for region in 0..N {
+            self.handle_pointing(region, House::Row);
+            self.handle_pointing(region, House::Col);
         }
+        self.deductions
     }
 
-    fn handle_pointing_on_square_col(&mut self, row: usize, col: usize) {
-        let notes = self.get_square_col_notes(row, col);
-        for (i, note) in Self::get_pointing_from_notes(notes).iter().enumerate() {
-            if *note != 0 {
-                self.use_pointing_set_on_col(*note, row, col + i);
+    fn handle_pointing(&mut self, region: usize, line: House) {
+        let segments = self.get_segments(region, line);
+        let notes: Vec<CandidateSet> = segments
+            .iter()
+            .map(|segment| self.get_segment_notes(segment))
+            .collect();
+        for (segment, note) in segments.iter().zip(Self::get_pointing_from_notes(&notes)) {
+            if !note.is_empty() {
+                self.use_pointing_set(note, region, line, segment);
             }
         }
     }
 
-    fn use_pointing_set_on_row(&mut self, note: u16, row: usize, col: usize) {
-        for i in 0..GRID_SIZE {
-            let is_different_col = i / 3 != col / 3;
-            if is_different_col {
-                let cell = &mut self.notes[row][i];
-                if (*cell & note) != 0 {
-                    self.progress = true;
+    fn use_pointing_set(
+        &mut self,
+        note: CandidateSet,
+        region: usize,
+        line: House,
+        segment: &[Point]
+    ) {
+        let layout = self.layout;
+        let line_cells = match line {
+            House::Row => layout.get_row_coordinates(segment[0].0),
+            _ => layout.get_col_coordinates(segment[0].1),
+        };
+        self.record(note, segment, |notes| {
+            let mut progress = false;
+            let outside = line_cells.iter().filter(|cell| layout.get_region(**cell) != region);
+            for (row, col) in outside {
+                let cell = &mut notes[*row][*col];
+                if !(*cell & note).is_empty() {
+                    progress = true;
                 }
-                *cell &= !note;
+                *cell -= note;
             }
-        }
+            progress
+        });
     }
 
-    fn use_pointing_set_on_col(&mut self, note: u16, row: usize, col: usize) {
-        for i in 0..GRID_SIZE {
-            let is_different_row = i / 3 != row / 3;
-            if is_different_row {
-                let cell = &mut self.notes[i][col];
-                if (*cell & note) != 0 {
-                    self.progress = true;
-                }
-                *cell &= !note;
-            }
-        }
-    }
-
-    fn get_square_row_notes(&self, row: usize, col: usize) -> [u16; 3] {
-        let mut notes: [u16; 3] = Default::default();
-        for (i, note) in notes.iter_mut().enumerate() {
-            *note = self.get_notes_on_single_square_row(row + i, col);
-        }
-        notes
+    fn record<F>(&mut self, note: CandidateSet, segment: &[Point], clear: F)
+        where F: FnOnce(&mut Notes<N>) -> bool
+    {
+        let reasons: Vec<Point> = segment
+            .iter()
+            .filter(|(row, col)| !(self.notes[*row][*col] & note).is_empty())
+            .copied()
+            .collect();
+        self.deductions.extend(Deduction::record(POINTING_SET, &reasons, self.notes, clear));
     }
 
-    fn get_square_col_notes(&self, row: usize, col: usize) -> [u16; 3] {
-        let mut notes: [u16; 3] = Default::default();
-        for (i, note) in notes.iter_mut().enumerate() {
-            *note = self.get_notes_on_single_square_col(col + i, row);
+    fn get_segments(&self, region: usize, line: House) -> Vec<Vec<Point>> {
+        let line_index = |(row, col): Point| if line == House::Row { row } else { col };
+        let mut segments: Vec<Vec<Point>> = Vec::new();
+        for cell in self.layout.get_region_coordinates(region) {
+            let index = line_index(cell);
+            match segments.iter_mut().find(|segment| line_index(segment[0]) == index) {
+                Some(segment) => segment.push(cell),
+                None => segments.push(vec![cell]),
+            }
         }
-        notes
+        segments
     }
 
-    fn get_notes_on_single_square_row(&self, row: usize, start_col: usize) -> u16 {
-        let mut row_notes = 0;
-        for col in Self::square_iter(start_col) {
-            row_notes |= self.notes[row][col];
-        }
-        row_notes
+    fn get_segment_notes(&self, segment: &[Point]) -> CandidateSet {
+        segment
+            .iter()
+            .fold(CandidateSet::EMPTY, |notes, (row, col)| notes | self.notes[*row][*col])
     }
 
-    fn get_notes_on_single_square_col(&self, col: usize, start_row: usize) -> u16 {
-        let mut row_notes = 0;
-        for row in Self::square_iter(start_row) {
-            row_notes |= self.notes[row][col];
+    fn get_pointing_from_notes(notes: &[CandidateSet]) -> Vec<CandidateSet> {
+        let mut pointing = Vec::new();
+        for (i, note) in notes.iter().enumerate() {
+            let others = notes
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold(CandidateSet::EMPTY, |others, (_, other)| others | *other);
+            pointing.push(*note - others);
         }
-        row_notes
-    }
-
-    fn get_pointing_from_notes(notes: [u16; 3]) -> [u16; 3] {
-        let mut pointing: [u16; 3] = Default::default();
-        pointing[0] = notes[0] & !(notes[1] | notes[2]);
-        pointing[1] = notes[1] & !(notes[0] | notes[2]);
-        pointing[2] = notes[2] & !(notes[1] | notes[0]);
         pointing
     }
-
-    fn square_iter(position: usize) -> [usize; 3] {
-        let start = position - (position % 3);
-        [start, start + 1, start + 2]
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::grid_size::GRID_SIZE;
+    use crate::solve::notes::from_bits;
 
-    #[test]
-    fn test_squre_iter() {
-        assert_eq!(Handler::square_iter(2), [0, 1, 2]);
-        assert_eq!(Handler::square_iter(3), [3, 4, 5]);
+    const JIGSAW_REGIONS: RegionMap = [
+        [0, 0, 0, 1, 1, 2, 2, 2, 2],
+        [0, 0, 0, 1, 1, 1, 2, 2, 2],
+        [0, 0, 3, 1, 1, 1, 1, 2, 2],
+        [0, 3, 3, 4, 4, 4, 4, 5, 5],
+        [3, 3, 3, 4, 4, 5, 5, 5, 5],
+        [3, 3, 3, 4, 4, 7, 5, 5, 5],
+        [6, 6, 6, 7, 4, 7, 8, 8, 8],
+        [6, 6, 6, 7, 7, 7, 8, 8, 8],
+        [6, 6, 6, 7, 7, 7, 8, 8, 8],
+    ];
+
+    fn get_pointing(notes: [u32; 3]) -> Vec<u32> {
+        Handler::<GRID_SIZE>::get_pointing_from_notes(&notes.map(CandidateSet::from_bits))
+            .into_iter()
+            .map(CandidateSet::bits)
+            .collect()
     }
 
     #[test]
     fn test_pointing() {
-        assert_eq!(Handler::get_pointing_from_notes([0, 0, 0]), [0, 0, 0]);
+        assert_eq!(get_pointing([0, 0, 0]), [0, 0, 0]);
         assert_eq!(
-            Handler::get_pointing_from_notes([0b111_111_111, 0b111_111_111, 0b111_111_111]),
+            get_pointing([0b111_111_111, 0b111_111_111, 0b111_111_111]),
             [0, 0, 0]
         );
         assert_eq!(
-            Handler::get_pointing_from_notes([0b110_110_011, 0b001_110_010, 0b001_001_001]),
+            get_pointing([0b110_110_011, 0b001_110_010, 0b001_001_001]),
             [0b_110_000_000, 0, 0b_000_001_000]
         );
     }
 
-    const NOTES1: Notes = [
+    #[test]
+    fn test_handle() {
+        let mut notes: Notes = Default::default();
+        notes[0][0] = CandidateSet::from_bits(0b000_000_011);
+        notes[0][1] = CandidateSet::from_bits(0b000_000_001);
+        notes[1][2] = CandidateSet::from_bits(0b000_000_010);
+        notes[0][5] = CandidateSet::from_bits(0b000_000_101);
+        let layout = Layout::default();
+        let deductions = Handler::new(&layout, &mut notes).handle();
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].technique, POINTING_SET);
+        assert_eq!(deductions[0].reasons, vec![(0, 0), (0, 1)]);
+        assert_eq!(deductions[0].eliminations, vec![((0, 5), 1)]);
+        assert_eq!(notes[0][5], CandidateSet::from_value(3));
+    }
+
+    #[test]
+    fn test_irregular_regions() {
+        let mut notes: Notes = Default::default();
+        notes[2][2] = CandidateSet::from_bits(0b000_000_001);
+        notes[3][2] = CandidateSet::from_bits(0b000_000_011);
+        notes[4][2] = CandidateSet::from_bits(0b000_000_010);
+        notes[6][0] = CandidateSet::from_bits(0b000_000_101);
+        notes[7][2] = CandidateSet::from_bits(0b000_000_101);
+        notes[8][1] = CandidateSet::from_bits(0b000_000_100);
+        let layout = Layout::from_regions(JIGSAW_REGIONS).unwrap();
+        let deductions = Handler::new(&layout, &mut notes).handle();
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].reasons, vec![(2, 2), (3, 2), (4, 2)]);
+        assert_eq!(deductions[0].eliminations, vec![((7, 2), 1)]);
+        assert_eq!(notes[4][2], CandidateSet::from_value(2));
+    }
+
+    const NOTES1: [[u32; GRID_SIZE]; GRID_SIZE] = [
         [
             0, 0, 0b000_001_011, 0b000_100_010, 0, 0b010_101_010, 0b110_001_001, 0b100_001_011,
             0b010_001_010,
@@ -168,11 +220,11 @@ mod tests {
         ],
     ];
 
-    fn check_notes(notes_index: usize, cross_index: usize, actual: u16, expected: u16, name: &str) {
+    fn check_notes(notes_index: usize, cross_index: usize, actual: u32, expected: u32, name: &str) {
         assert_eq!(
             actual,
             expected,
-            "Wrong notes for {} {}, square starting on {}, actual {:b}, expected {:b}",
+            "Wrong notes for {} {}, segment crossing {}, actual {:b}, expected {:b}",
             name,
             notes_index,
             cross_index,
@@ -181,29 +233,49 @@ mod tests {
         );
     }
 
-    fn check_row_notes(sut: &mut Handler, row: usize, col: usize, expected: u16) {
-        let actual = sut.get_notes_on_single_square_row(row, col);
+    fn get_segments_notes(sut: &Handler<GRID_SIZE>, region: usize, line: House) -> Vec<u32> {
+        sut.get_segments(region, line)
+            .iter()
+            .map(|segment| sut.get_segment_notes(segment).bits())
+            .collect()
+    }
+
+    fn get_line_notes(sut: &Handler<GRID_SIZE>, cell: Point, line: House) -> u32 {
+        let segments = sut.get_segments(sut.layout.get_region(cell), line);
+        let segment = segments.iter().find(|segment| segment.contains(&cell)).unwrap();
+        sut.get_segment_notes(segment).bits()
+    }
+
+    fn check_row_notes(sut: &mut Handler<GRID_SIZE>, row: usize, col: usize, expected: u32) {
+        let actual = get_line_notes(sut, (row, col), House::Row);
         check_notes(row, col, actual, expected, "row");
     }
 
-    fn check_col_notes(sut: &mut Handler, row: usize, col: usize, expected: u16) {
-        let actual = sut.get_notes_on_single_square_col(col, row);
+    fn check_col_notes(sut: &mut Handler<GRID_SIZE>, row: usize, col: usize, expected: u32) {
+        let actual = get_line_notes(sut, (row, col), House::Col);
         check_notes(col, row, actual, expected, "col");
     }
 
     #[test]
     fn test_get_notes() {
-        let mut binding = NOTES1;
-        let mut sut = Handler::new(&mut binding);
+        let layout = Layout::default();
+        let mut binding = from_bits(&NOTES1);
+        let mut sut = Handler::new(&layout, &mut binding);
         check_row_notes(&mut sut, 0, 0, 0b000_001_011);
         check_row_notes(&mut sut, 1, 0, 0b001_001_010);
         check_row_notes(&mut sut, 2, 0, 0b000_000_011);
-        assert_eq!(sut.get_square_row_notes(0, 0), [0b000_001_011, 0b001_001_010, 0b000_000_011]);
+        assert_eq!(
+            get_segments_notes(&sut, 0, House::Row),
+            [0b000_001_011, 0b001_001_010, 0b000_000_011]
+        );
 
         check_col_notes(&mut sut, 0, 0, 0b000_000_011);
         check_col_notes(&mut sut, 0, 1, 0b001_001_010);
         check_col_notes(&mut sut, 0, 2, 0b001_001_011);
-        assert_eq!(sut.get_square_col_notes(0, 0), [0b000_000_011, 0b001_001_010, 0b001_001_011]);
+        assert_eq!(
+            get_segments_notes(&sut, 0, House::Col),
+            [0b000_000_011, 0b001_001_010, 0b001_001_011]
+        );
 
         check_row_notes(&mut sut, 2, 3, 0b000_001_110);
         check_col_notes(&mut sut, 0, 6, 0b111_011_101);
@@ -0,0 +1,290 @@
+use std::fmt;
+use crate::common::puzzle::Puzzle;
+use crate::solve::coordinates::Layout;
+use crate::solve::deduction::*;
+use crate::solve::solution_count::*;
+use crate::solve::solver::Solver;
+use crate::solve::validation::SolverError;
+
+const GUESS_DIFFICULTY: f32 = 10.0;
+
+const DIFFICULTIES: [(&str, f32); 47] = [
+    ("Solved Cell", 1.0),
+    ("Hidden Single", 1.5),
+    ("Naked Single", 2.3),
+    ("Cage Combination", 2.4),
+    ("Thermometer", 2.4),
+    ("Palindrome", 2.4),
+    ("German Whispers", 2.4),
+    ("Arrow", 2.5),
+    ("Pointing Set", 2.6),
+    ("Innies and Outies", 2.8),
+    ("Naked Pair", 3.0),
+    ("X-Wing", 3.2),
+    ("Hidden Pair", 3.4),
+    ("Naked Triple", 3.6),
+    ("Finned X-Wing", 3.6),
+    ("Sashimi X-Wing", 3.7),
+    ("Swordfish", 3.8),
+    ("Hidden Triple", 4.0),
+    ("Skyscraper", 4.0),
+    ("2-String Kite", 4.1),
+    ("XY-Wing", 4.2),
+    ("Finned Swordfish", 4.2),
+    ("Empty Rectangle", 4.3),
+    ("Sashimi Swordfish", 4.3),
+    ("XYZ-Wing", 4.4),
+    ("W-Wing", 4.4),
+    ("Unique Rectangle Type 1", 4.5),
+    ("Unique Rectangle Type 2", 4.5),
+    ("Unique Rectangle Type 4", 4.5),
+    ("Unique Rectangle Type 3", 4.6),
+    ("Colour Trap", 4.5),
+    ("Colour Wrap", 4.5),
+    ("Multi-Colouring", 4.7),
+    ("Naked Quad", 5.0),
+    ("Sue de Coq", 5.0),
+    ("Jellyfish", 5.2),
+    ("Hidden Quad", 5.4),
+    ("Finned Jellyfish", 5.4),
+    ("Sashimi Jellyfish", 5.5),
+    ("ALS-XZ", 5.5),
+    ("BUG+1", 5.6),
+    ("ALS-XY-Wing", 6.0),
+    ("X-Chain", 6.5),
+    ("XY-Chain", 6.6),
+    ("Alternating Inference Chain", 7.0),
+    ("Discontinuous Nice Loop", 7.2),
+    ("Guess", GUESS_DIFFICULTY),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Tier {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+    Extreme,
+}
+
+const TIER_LIMITS: [(f32, Tier); 4] = [
+    (2.5, Tier::Easy),
+    (3.5, Tier::Medium),
+    (4.5, Tier::Hard),
+    (7.0, Tier::Expert),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rating {
+    pub tier: Tier,
+    pub score: f32,
+    pub hardest: Option<&'static str>,
+    pub steps: Vec<(&'static str, usize)>,
+}
+
+fn find_difficulty(technique: &str) -> Option<f32> {
+    DIFFICULTIES
+        .iter()
+        .find(|(name, _)| *name == technique)
+        .map(|(_, difficulty)| *difficulty)
+}
+
+pub fn get_difficulty(technique: &str) -> f32 {
+    find_difficulty(technique).unwrap_or(GUESS_DIFFICULTY)
+}
+
+pub fn rate<const N: usize>(
+    layout: &Layout<N>,
+    puzzle: &Puzzle<N>
+) -> Result<Rating, SolverError> {
+    let mut solver = Solver::with_layout(layout.clone(), *puzzle)?;
+    solver.set_uniqueness(count_solutions(layout, puzzle, DEFAULT_CAP)?.is_unique());
+    solver.set_tracing(true);
+    solver.solve()?;
+    Ok(rate_trace(solver.get_trace()))
+}
+
+fn rate_trace<'a, I>(trace: I) -> Rating where I: Iterator<Item = &'a Deduction> {
+    let mut steps: Vec<(&'static str, usize)> = Vec::new();
+    for deduction in trace {
+        match steps.iter_mut().find(|(technique, _)| *technique == deduction.technique) {
+            Some((_, count)) => *count += 1,
+            None => steps.push((deduction.technique, 1)),
+        }
+    }
+    steps.sort_by(|(first, _), (second, _)| {
+        get_difficulty(first).total_cmp(&get_difficulty(second))
+    });
+    let hardest = steps.last().map(|(technique, _)| *technique);
+    let score = hardest.map_or(0.0, get_difficulty);
+    let tier = TIER_LIMITS
+        .iter()
+        .find(|(limit, _)| score < *limit)
+        .map_or(Tier::Extreme, |(_, tier)| *tier);
+    Rating { tier, score, hardest, steps }
+}
+
+impl fmt::Display for Tier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Tier::Easy => "easy",
+            Tier::Medium => "medium",
+            Tier::Hard => "hard",
+            Tier::Expert => "expert",
+            Tier::Extreme => "extreme",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Difficulty: {} ({:.1})", self.tier, self.score)?;
+        if let Some(hardest) = self.hardest {
+            write!(f, "\nHardest technique: {}", hardest)?;
+        }
+        for (technique, count) in self.steps.iter() {
+            write!(f, "\n{}: {}", technique, count)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: Puzzle = [
+        [5, 3, 0, 0, 7, 0, 0, 0, 0],
+        [6, 0, 0, 1, 9, 5, 0, 0, 0],
+        [0, 9, 8, 0, 0, 0, 0, 6, 0],
+        [8, 0, 0, 0, 6, 0, 0, 0, 3],
+        [4, 0, 0, 8, 0, 3, 0, 0, 1],
+        [7, 0, 0, 0, 2, 0, 0, 0, 6],
+        [0, 6, 0, 0, 0, 0, 2, 8, 0],
+        [0, 0, 0, 4, 1, 9, 0, 0, 5],
+        [0, 0, 0, 0, 8, 0, 0, 7, 9],
+    ];
+
+    fn deduction(technique: &'static str) -> Deduction {
+        Deduction::placement(technique, (0, 0), 1)
+    }
+
+    #[test]
+    fn test_difficulty() {
+        assert_eq!(get_difficulty("Naked Single"), 2.3);
+        assert_eq!(get_difficulty("Guess"), GUESS_DIFFICULTY);
+        assert!(get_difficulty("X-Wing") < get_difficulty("Swordfish"));
+        assert!(get_difficulty("XY-Wing") < get_difficulty("XY-Chain"));
+    }
+
+    #[test]
+    fn test_unknown_technique() {
+        assert_eq!(find_difficulty("X-Wings"), None);
+        assert_eq!(get_difficulty("X-Wings"), GUESS_DIFFICULTY);
+    }
+
+    #[test]
+    fn test_all_techniques_rated() {
+        use crate::solve::als::AlsKind;
+        use crate::solve::chains::ChainKind;
+        use crate::solve::colouring::ColouringKind;
+        use crate::solve::lines::LineKind;
+        use crate::solve::short_chains::ShortChainKind;
+        use crate::solve::uniqueness::UniquenessKind;
+        use crate::solve::wings::WingKind;
+        let mut names = vec![SOLVED_PEERS, HIDDEN_SINGLE, NAKED_SINGLE, GUESS];
+        names.push(crate::solve::pointing_sets::POINTING_SET);
+        names.push(crate::solve::cages::CAGE_COMBINATION);
+        names.push(crate::solve::cages::INNIES_AND_OUTIES);
+        names.extend(crate::solve::hidden_sets::NAKED_SETS);
+        names.extend(crate::solve::hidden_sets::HIDDEN_SETS);
+        names.extend(crate::solve::fish::FISH_NAMES.iter().flatten());
+        names.extend([WingKind::Xy, WingKind::Xyz].map(|kind| kind.name()));
+        names.extend([
+            ShortChainKind::Skyscraper,
+            ShortChainKind::TwoStringKite,
+            ShortChainKind::EmptyRectangle,
+            ShortChainKind::WWing,
+        ].map(|kind| kind.name()));
+        names.extend([
+            ColouringKind::ColourWrap,
+            ColouringKind::ColourTrap,
+            ColouringKind::MultiColouring,
+        ].map(|kind| kind.name()));
+        names.extend([
+            UniquenessKind::UniqueRectangle1,
+            UniquenessKind::UniqueRectangle2,
+            UniquenessKind::UniqueRectangle3,
+            UniquenessKind::UniqueRectangle4,
+            UniquenessKind::BugPlusOne,
+        ].map(|kind| kind.name()));
+        names.extend(
+            [AlsKind::AlsXz, AlsKind::AlsXyWing, AlsKind::SueDeCoq].map(|kind| kind.name())
+        );
+        names.extend([
+            ChainKind::XChain,
+            ChainKind::XyChain,
+            ChainKind::Aic,
+            ChainKind::DiscontinuousNiceLoop,
+        ].map(|kind| kind.name()));
+        names.extend([
+            LineKind::Thermometer,
+            LineKind::Arrow,
+            LineKind::Palindrome,
+            LineKind::Whispers,
+        ].map(|kind| kind.name()));
+        assert_eq!(names.len(), DIFFICULTIES.len());
+        for name in names {
+            assert!(find_difficulty(name).is_some(), "{} is not rated", name);
+        }
+        let pipeline: crate::solve::technique::Pipeline = Default::default();
+        assert!(pipeline.iter().all(|technique| technique.difficulty() < GUESS_DIFFICULTY));
+    }
+
+    #[test]
+    fn test_rate_trace() {
+        let trace = [
+            deduction("Naked Single"),
+            deduction("XY-Wing"),
+            deduction("Hidden Single"),
+            deduction("Naked Single"),
+        ];
+        let rating = rate_trace(trace.iter());
+        assert_eq!(rating.tier, Tier::Hard);
+        assert_eq!(rating.score, 4.2);
+        assert_eq!(rating.hardest, Some("XY-Wing"));
+        assert_eq!(
+            rating.steps,
+            vec![("Hidden Single", 1), ("Naked Single", 2), ("XY-Wing", 1)]
+        );
+    }
+
+    #[test]
+    fn test_tiers() {
+        let tier = |technique| rate_trace([deduction(technique)].iter()).tier;
+        assert_eq!(tier("Hidden Single"), Tier::Easy);
+        assert_eq!(tier("Pointing Set"), Tier::Medium);
+        assert_eq!(tier("Swordfish"), Tier::Hard);
+        assert_eq!(tier("ALS-XZ"), Tier::Expert);
+        assert_eq!(tier("Guess"), Tier::Extreme);
+        assert_eq!(rate_trace([].iter()).tier, Tier::Easy);
+    }
+
+    #[test]
+    fn test_rate() {
+        let rating = rate(&Layout::default(), &PUZZLE).unwrap();
+        assert_eq!(rating.tier, Tier::Easy);
+        assert_eq!(rating.hardest, Some("Naked Single"));
+        let placed: usize = rating.steps.iter().map(|(_, count)| count).sum();
+        assert_eq!(placed, 51);
+    }
+
+    #[test]
+    fn test_display() {
+        let rating = rate_trace([deduction("Naked Single"), deduction("X-Wing")].iter());
+        let expected = "Difficulty: medium (3.2)\nHardest technique: X-Wing\n\
+            Naked Single: 1\nX-Wing: 1";
+        assert_eq!(rating.to_string(), expected);
+    }
+}
@@ -0,0 +1,337 @@
+use crate::solve::notes::*;
+use crate::solve::candidate_set::CandidateSet;
+use crate::solve::coordinates::*;
+use crate::solve::map_notes::*;
+use crate::solve::deduction::Deduction;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShortChainKind {
+    Skyscraper,
+    TwoStringKite,
+    EmptyRectangle,
+    WWing,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ShortChain {
+    pub kind: ShortChainKind,
+    pub value: usize,
+    pub cells: Vec<Point>,
+    pub eliminations: Vec<Point>,
+}
+
+impl ShortChainKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ShortChainKind::Skyscraper => "Skyscraper",
+            ShortChainKind::TwoStringKite => "2-String Kite",
+            ShortChainKind::EmptyRectangle => "Empty Rectangle",
+            ShortChainKind::WWing => "W-Wing",
+        }
+    }
+}
+
+impl ShortChain {
+    pub fn clear<const N: usize>(&self, notes: &mut Notes<N>) -> bool {
+        clear_value(notes, self.value, &self.eliminations)
+    }
+
+    pub fn apply<const N: usize>(&self, notes: &mut Notes<N>) -> Option<Deduction> {
+        Deduction::record(self.kind.name(), &self.cells, notes, |notes| self.clear(notes))
+    }
+}
+
+pub fn use_short_chains<const N: usize>(
+    layout: &Layout<N>,
+    notes: &mut Notes<N>
+) -> Vec<Deduction> {
+    for finder in [find_turbot_fish, find_empty_rectangles, find_w_wings] {
+        let deductions: Vec<Deduction> = finder(layout, notes)
+            .iter()
+            .filter_map(|chain| chain.apply(notes))
+            .collect();
+        if !deductions.is_empty() {
+            return deductions;
+        }
+    }
+    Vec::new()
+}
+
+pub fn find_turbot_fish<const N: usize>(layout: &Layout<N>, notes: &Notes<N>) -> Vec<ShortChain> {
+    let mut found = Vec::new();
+    for value in 1..=N {
+        let links = get_strong_links(layout, notes, value);
+        for (index, first) in links.iter().enumerate() {
+            for second in links[index + 1..].iter() {
+                found.extend(check_turbot_fish(layout, notes, value, first, second));
+            }
+        }
+    }
+    found
+}
+
+fn check_turbot_fish<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize,
+    first: &StrongLink,
+    second: &StrongLink
+) -> Option<ShortChain> {
+    for (first_end, second_end) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+        let (joint, end) = (first.ends[first_end], first.ends[1 - first_end]);
+        let (other_joint, other_end) = (second.ends[second_end], second.ends[1 - second_end]);
+        let cells = vec![end, joint, other_joint, other_end];
+        if (1..cells.len()).any(|index| cells[..index].contains(&cells[index])) {
+            return None;
+        }
+        let houses = (first.house, second.house);
+        let Some(kind) = get_turbot_kind(layout, houses, joint, other_joint) else {
+            continue;
+        };
+        let eliminations = get_common_peers(layout, notes, value, &[end, other_end], &cells);
+        if !eliminations.is_empty() {
+            return Some(ShortChain { kind, value, cells, eliminations });
+        }
+    }
+    None
+}
+
+fn get_turbot_kind<const N: usize>(
+    layout: &Layout<N>,
+    (first, second): (House, House),
+    joint: Point,
+    other_joint: Point
+) -> Option<ShortChainKind> {
+    let same_region = layout.get_region(joint) == layout.get_region(other_joint);
+    match (first, second) {
+        (House::Row, House::Row) if joint.1 == other_joint.1 => Some(ShortChainKind::Skyscraper),
+        (House::Col, House::Col) if joint.0 == other_joint.0 => Some(ShortChainKind::Skyscraper),
+        (House::Row, House::Col) | (House::Col, House::Row) if same_region => {
+            Some(ShortChainKind::TwoStringKite)
+        }
+        _ => None,
+    }
+}
+
+pub fn find_empty_rectangles<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>
+) -> Vec<ShortChain> {
+    let mut found = Vec::new();
+    for value in 1..=N {
+        let links = get_strong_links(layout, notes, value);
+        for region in 0..N {
+            let region_cells = layout.get_region_coordinates(region);
+            let cells = get_cells_with_value(notes, value, &region_cells);
+            for (row, col) in region_cells.iter() {
+                if is_empty_rectangle(&cells, (*row, *col)) {
+                    found.extend(
+                        check_empty_rectangle(layout, notes, value, &links, &cells, (*row, *col))
+                    );
+                }
+            }
+        }
+    }
+    found
+}
+
+fn is_empty_rectangle(cells: &[Point], (row, col): Point) -> bool {
+    let on_row = cells.iter().any(|cell| cell.0 == row && cell.1 != col);
+    let on_col = cells.iter().any(|cell| cell.1 == col && cell.0 != row);
+    on_row && on_col && cells.iter().all(|cell| cell.0 == row || cell.1 == col)
+}
+
+fn check_empty_rectangle<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize,
+    links: &[StrongLink],
+    region_cells: &[Point],
+    (row, col): Point
+) -> Vec<ShortChain> {
+    let region = layout.get_region((row, col));
+    let mut found = Vec::new();
+    for link in links {
+        for (joint, end) in [(link.ends[0], link.ends[1]), (link.ends[1], link.ends[0])] {
+            let target = match link.house {
+                House::Col if joint.0 == row && joint.1 != col => (end.0, col),
+                House::Row if joint.1 == col && joint.0 != row => (row, end.1),
+                _ => {
+                    continue;
+                }
+            };
+            let is_outside = [joint, target].iter().all(|cell| layout.get_region(*cell) != region);
+            if is_outside && notes[target.0][target.1].contains(value) {
+                let mut cells = region_cells.to_vec();
+                cells.extend([joint, end]);
+                found.push(ShortChain {
+                    kind: ShortChainKind::EmptyRectangle,
+                    value,
+                    cells,
+                    eliminations: vec![target],
+                });
+            }
+        }
+    }
+    found
+}
+
+pub fn find_w_wings<const N: usize>(layout: &Layout<N>, notes: &Notes<N>) -> Vec<ShortChain> {
+    let mut bivalues = Vec::new();
+    for (row, notes_row) in notes.iter().enumerate() {
+        for (col, note) in notes_row.iter().enumerate() {
+            if note.count() == 2 {
+                bivalues.push((row, col));
+            }
+        }
+    }
+    let mut found = Vec::new();
+    for (index, first) in bivalues.iter().enumerate() {
+        for second in bivalues[index + 1..].iter() {
+            let note = notes[first.0][first.1];
+            if note == notes[second.0][second.1] && !layout.sees(*first, *second) {
+                found.extend(check_w_wing(layout, notes, note, *first, *second));
+            }
+        }
+    }
+    found
+}
+
+fn check_w_wing<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    note: CandidateSet,
+    first: Point,
+    second: Point
+) -> Option<ShortChain> {
+    let (Some(low), Some(high)) = (note.min(), note.max()) else {
+        return None;
+    };
+    for (linked, value) in [(low, high), (high, low)] {
+        for link in get_strong_links(layout, notes, linked) {
+            let [start, end] = link.ends;
+            if [start, end].contains(&first) || [start, end].contains(&second) {
+                continue;
+            }
+            let is_bridge =
+                (layout.sees(start, first) && layout.sees(end, second)) ||
+                (layout.sees(start, second) && layout.sees(end, first));
+            if !is_bridge {
+                continue;
+            }
+            let cells = vec![first, start, end, second];
+            let eliminations = get_common_peers(layout, notes, value, &[first, second], &cells);
+            if !eliminations.is_empty() {
+                return Some(ShortChain { kind: ShortChainKind::WWing, value, cells, eliminations });
+            }
+        }
+    }
+    None
+}
+
+fn get_cells_with_value<const N: usize>(
+    notes: &Notes<N>,
+    value: usize,
+    coordinates: &Coordinates<N>
+) -> Vec<Point> {
+    coordinates
+        .iter()
+        .filter(|(row, col)| notes[*row][*col].contains(value))
+        .copied()
+        .collect()
+}
+
+fn get_common_peers<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize,
+    ends: &[Point; 2],
+    excluded: &[Point]
+) -> Vec<Point> {
+    layout.get_peers(ends[0])
+        .into_iter()
+        .filter(|point| layout.sees(ends[1], *point) && !excluded.contains(point))
+        .filter(|(row, col)| notes[*row][*col].contains(value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::grid_size::GRID_SIZE;
+
+    fn notes_with(value: usize, cells: &[Point]) -> Notes {
+        let mut notes: Notes = [[CandidateSet::EMPTY; GRID_SIZE]; GRID_SIZE];
+        for (row, col) in cells {
+            notes[*row][*col].insert(value);
+        }
+        notes
+    }
+
+    #[test]
+    fn test_skyscraper() {
+        let layout = Layout::default();
+        let mut notes = notes_with(1, &[(1, 0), (1, 4), (6, 0), (6, 5), (2, 5), (7, 4), (4, 8)]);
+        let skyscraper = ShortChain {
+            kind: ShortChainKind::Skyscraper,
+            value: 1,
+            cells: vec![(1, 4), (1, 0), (6, 0), (6, 5)],
+            eliminations: vec![(7, 4), (2, 5)],
+        };
+        assert!(find_turbot_fish(&layout, &notes).contains(&skyscraper));
+        assert!(!use_short_chains(&layout, &mut notes).is_empty());
+        assert!(notes[2][5].is_empty());
+        assert!(notes[7][4].is_empty());
+        assert_eq!(notes[4][8], CandidateSet::from_value(1));
+    }
+
+    #[test]
+    fn test_two_string_kite() {
+        let layout = Layout::default();
+        let notes = notes_with(2, &[(0, 1), (0, 6), (2, 0), (5, 0), (5, 6), (4, 4)]);
+        let kite = ShortChain {
+            kind: ShortChainKind::TwoStringKite,
+            value: 2,
+            cells: vec![(0, 6), (0, 1), (2, 0), (5, 0)],
+            eliminations: vec![(5, 6)],
+        };
+        assert!(find_turbot_fish(&layout, &notes).contains(&kite));
+    }
+
+    #[test]
+    fn test_empty_rectangle() {
+        let layout = Layout::default();
+        let mut notes = notes_with(
+            3,
+            &[(3, 4), (4, 3), (4, 5), (4, 8), (0, 8), (0, 4), (0, 1), (7, 1)]
+        );
+        let chains = find_empty_rectangles(&layout, &notes);
+        assert!(!chains.is_empty());
+        assert!(chains.iter().all(|chain| chain.kind == ShortChainKind::EmptyRectangle));
+        assert!(chains.iter().all(|chain| chain.eliminations == vec![(0, 4)]));
+        assert!(chains[0].clear(&mut notes));
+        assert!(notes[0][4].is_empty());
+    }
+
+    #[test]
+    fn test_w_wing() {
+        let layout = Layout::default();
+        let mut notes: Notes = [[CandidateSet::EMPTY; GRID_SIZE]; GRID_SIZE];
+        notes[0][0] = CandidateSet::from_bits(0b000_000_011);
+        notes[4][5] = CandidateSet::from_bits(0b000_000_011);
+        notes[2][0] = CandidateSet::from_bits(0b000_000_101);
+        notes[2][5] = CandidateSet::from_bits(0b000_000_101);
+        notes[0][5] = CandidateSet::from_bits(0b000_001_010);
+        notes[4][0] = CandidateSet::from_bits(0b000_001_010);
+        let w_wing = ShortChain {
+            kind: ShortChainKind::WWing,
+            value: 2,
+            cells: vec![(0, 0), (2, 0), (2, 5), (4, 5)],
+            eliminations: vec![(0, 5), (4, 0)],
+        };
+        assert_eq!(find_w_wings(&layout, &notes), vec![w_wing]);
+        assert!(!use_short_chains(&layout, &mut notes).is_empty());
+        assert_eq!(notes[0][5].bits(), 0b000_001_000);
+        assert_eq!(notes[4][0].bits(), 0b000_001_000);
+    }
+}
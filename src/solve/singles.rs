@@ -0,0 +1,80 @@
+use crate::solve::notes::*;
+use crate::solve::coordinates::*;
+use crate::solve::deduction::*;
+use crate::solve::candidate_set::CandidateSet;
+
+pub fn use_naked_singles<const N: usize>(
+    layout: &Layout<N>,
+    notes: &mut Notes<N>
+) -> Vec<Deduction> {
+    let mut deductions = Vec::new();
+    for row in 0..N {
+        for col in 0..N {
+            if let Some(value) = notes[row][col].get_single() {
+                place(layout, notes, (row, col), value as u8);
+                deductions.push(Deduction::placement(NAKED_SINGLE, (row, col), value as u8));
+            }
+        }
+    }
+    deductions
+}
+
+pub fn use_hidden_singles<const N: usize>(
+    layout: &Layout<N>,
+    notes: &mut Notes<N>
+) -> Vec<Deduction> {
+    let mut deductions = Vec::new();
+    for value in 1..=N {
+        layout.perform_for_all_sets(|coordinates| {
+            let mut cells = coordinates
+                .iter()
+                .filter(|(row, col)| notes[*row][*col].contains(value));
+            if let (Some(cell), None) = (cells.next().copied(), cells.next()) {
+                place(layout, notes, cell, value as u8);
+                deductions.push(Deduction::placement(HIDDEN_SINGLE, cell, value as u8));
+                return true;
+            }
+            false
+        });
+    }
+    deductions
+}
+
+fn place<const N: usize>(layout: &Layout<N>, notes: &mut Notes<N>, (row, col): Point, value: u8) {
+    notes[row][col] = CandidateSet::EMPTY;
+    clear_placed(layout, notes, (row, col), value as usize);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::grid_size::GRID_SIZE;
+
+    #[test]
+    fn test_naked_singles() {
+        let mut notes: Notes = Default::default();
+        notes[0][0] = CandidateSet::from_bits(0b000_000_100);
+        notes[0][5] = CandidateSet::from_bits(0b000_000_110);
+        notes[4][4] = CandidateSet::from_bits(0b000_000_110);
+        let deductions = use_naked_singles(&Layout::default(), &mut notes);
+        let expected = vec![
+            Deduction::placement(NAKED_SINGLE, (0, 0), 3),
+            Deduction::placement(NAKED_SINGLE, (0, 5), 2),
+        ];
+        assert_eq!(deductions, expected);
+        assert!(notes[0][0].is_empty());
+        assert!(notes[0][5].is_empty());
+        assert_eq!(notes[4][4].bits(), 0b000_000_110);
+    }
+
+    #[test]
+    fn test_hidden_singles() {
+        let mut notes: Notes = [[CandidateSet::from_bits(0b000_000_011); GRID_SIZE]; GRID_SIZE];
+        notes[8][8] = CandidateSet::from_bits(0b000_000_111);
+        let deductions = use_hidden_singles(&Layout::default(), &mut notes);
+        assert_eq!(deductions, vec![Deduction::placement(HIDDEN_SINGLE, (8, 8), 3)]);
+        assert!(notes[8][8].is_empty());
+        assert_eq!(notes[8][0].bits(), 0b000_000_011);
+        assert!(use_hidden_singles(&Layout::default(), &mut notes).is_empty());
+    }
+}
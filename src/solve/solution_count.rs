@@ -0,0 +1,106 @@
+use crate::common::grid_size::GRID_SIZE;
+use crate::common::puzzle::Puzzle;
+use crate::solve::coordinates::Layout;
+use crate::solve::dancing_links::DancingLinks;
+use crate::solve::validation::SolverError;
+
+pub const DEFAULT_CAP: usize = 2;
+
+#[derive(Debug, PartialEq)]
+pub enum SolutionCount<const N: usize = GRID_SIZE> {
+    NoSolution,
+    Unique(Puzzle<N>),
+    Multiple { count: usize, solutions: [Puzzle<N>; 2] },
+}
+
+impl<const N: usize> SolutionCount<N> {
+    pub fn is_unique(&self) -> bool {
+        matches!(self, SolutionCount::Unique(_))
+    }
+}
+
+pub fn count_solutions<const N: usize>(
+    layout: &Layout<N>,
+    puzzle: &Puzzle<N>,
+    cap: usize
+) -> Result<SolutionCount<N>, SolverError> {
+    if cap < DEFAULT_CAP {
+        return Err(SolverError::CapTooSmall { cap });
+    }
+    let solutions = DancingLinks::new(layout, puzzle).find_solutions(cap);
+    Ok(match solutions.len() {
+        0 => SolutionCount::NoSolution,
+        1 => SolutionCount::Unique(solutions[0]),
+        count => SolutionCount::Multiple { count, solutions: [solutions[0], solutions[1]] },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOLUTION: Puzzle = [
+        [8, 1, 2, 7, 5, 3, 6, 4, 9],
+        [9, 4, 3, 6, 8, 2, 1, 7, 5],
+        [6, 7, 5, 4, 9, 1, 2, 8, 3],
+        [1, 5, 4, 2, 3, 7, 8, 9, 6],
+        [3, 6, 9, 8, 4, 5, 7, 2, 1],
+        [2, 8, 7, 1, 6, 9, 5, 3, 4],
+        [5, 2, 1, 9, 7, 4, 3, 6, 8],
+        [4, 3, 8, 5, 2, 6, 9, 1, 7],
+        [7, 9, 6, 3, 1, 8, 4, 5, 2],
+    ];
+
+    fn with_deadly_pattern() -> Puzzle {
+        let mut puzzle = SOLUTION;
+        for (row, col) in [(0, 2), (0, 5), (1, 2), (1, 5)] {
+            puzzle[row][col] = 0;
+        }
+        puzzle
+    }
+
+    #[test]
+    fn test_unique() {
+        let layout = Layout::default();
+        let mut puzzle = SOLUTION;
+        puzzle[4][4] = 0;
+        puzzle[8][0] = 0;
+        let count = count_solutions(&layout, &puzzle, DEFAULT_CAP).unwrap();
+        assert_eq!(count, SolutionCount::Unique(SOLUTION));
+        assert!(count.is_unique());
+    }
+
+    #[test]
+    fn test_no_solution() {
+        let layout = Layout::default();
+        let mut puzzle = with_deadly_pattern();
+        puzzle[0][2] = 8;
+        let count = count_solutions(&layout, &puzzle, DEFAULT_CAP);
+        assert_eq!(count, Ok(SolutionCount::NoSolution));
+    }
+
+    #[test]
+    fn test_two_solutions() {
+        let layout = Layout::default();
+        let mut swapped = SOLUTION;
+        swapped[0][2] = 3;
+        swapped[0][5] = 2;
+        swapped[1][2] = 2;
+        swapped[1][5] = 3;
+        let expected = SolutionCount::Multiple { count: 2, solutions: [SOLUTION, swapped] };
+        let puzzle = with_deadly_pattern();
+        assert_eq!(count_solutions(&layout, &puzzle, DEFAULT_CAP).unwrap(), expected);
+        assert_eq!(count_solutions(&layout, &puzzle, 10).unwrap(), expected);
+        assert!(!expected.is_unique());
+    }
+
+    #[test]
+    fn test_cap() {
+        let layout = Layout::default();
+        let empty: Puzzle = [[0; GRID_SIZE]; GRID_SIZE];
+        let count = count_solutions(&layout, &empty, 10).unwrap();
+        assert!(matches!(count, SolutionCount::Multiple { count: 10, .. }));
+        assert_eq!(count_solutions(&layout, &empty, 1), Err(SolverError::CapTooSmall { cap: 1 }));
+        assert_eq!(count_solutions(&layout, &empty, 0), Err(SolverError::CapTooSmall { cap: 0 }));
+    }
+}
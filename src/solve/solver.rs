@@ -1,74 +1,107 @@
 use crate::common::grid_size::GRID_SIZE;
 use crate::common::puzzle::Puzzle;
-use crate::solve::coordinates::*;
+use crate::solve::coordinates::Layout;
 use crate::solve::notes_manager::NotesManager;
+use crate::solve::validation::*;
+use crate::solve::deduction::*;
+use crate::solve::technique::Pipeline;
 
-pub struct Solver {
-    puzzle: Puzzle,
-    notes_manager: NotesManager,
+pub struct Solver<const N: usize = GRID_SIZE> {
+    puzzle: Puzzle<N>,
+    notes_manager: NotesManager<N>,
+    uniqueness: bool,
+    guessed: bool,
+    tracing: bool,
+    trace: Vec<Deduction>,
+    pipeline: Pipeline<N>,
 }
 
-impl Solver {
-    pub fn new(play_board: Puzzle) -> Self {
-        let mut filler = NotesManager::new(play_board);
-        filler.fill();
-        Solver {
+impl<const N: usize> Solver<N> {
+    #[cfg(test)]
+    pub fn new(play_board: Puzzle<N>) -> Result<Self, SolverError> {
+        Self::with_layout(Layout::default(), play_board)
+    }
+
+    pub fn with_layout(layout: Layout<N>, play_board: Puzzle<N>) -> Result<Self, SolverError> {
+        let mut filler = NotesManager::with_layout(layout, play_board);
+        filler.fill()?;
+        Ok(Solver {
             puzzle: play_board,
             notes_manager: filler,
-        }
+            uniqueness: true,
+            guessed: false,
+            tracing: false,
+            trace: Vec::new(),
+            pipeline: Pipeline::default(),
+        })
+    }
+
+    pub fn set_uniqueness(&mut self, enabled: bool) {
+        self.uniqueness = enabled;
     }
 
-    pub fn get_solution(&self) -> Puzzle {
+    pub fn set_pipeline(&mut self, pipeline: Pipeline<N>) {
+        self.pipeline = pipeline;
+    }
+
+    pub fn set_tracing(&mut self, enabled: bool) {
+        self.tracing = enabled;
+    }
+
+    pub fn get_trace(&self) -> impl Iterator<Item = &Deduction> {
+        self.trace.iter()
+    }
+
+    pub fn get_solution(&self) -> Puzzle<N> {
         self.puzzle
     }
 
-    pub fn solve(&mut self) {
-        let iterations_limit = 100;
-        let mut iterations_counter = 0;
-        while
-            iterations_counter < iterations_limit &&
-            (self.set_obvious_ones() ||
-                self.set_hiden_ones() ||
-                self.notes_manager.set_obvious_pairs() ||
-                self.notes_manager.use_square_methods())
-        {
-            iterations_counter += 1;
-        }
+    pub fn needed_guessing(&self) -> bool {
+        self.guessed
     }
 
-    fn set_obvious_ones(&mut self) -> bool {
-        let mut any_cell_filled: bool = false;
-        let notes = self.notes_manager.get();
-        for (row, notes_row) in notes.iter().enumerate() {
-            for (col, note) in notes_row.iter().enumerate() {
-                if note.count_ones() == 1 {
-                    let value = (note.trailing_zeros() + 1) as u8;
-                    self.set(row, col, value);
-                    any_cell_filled = true;
-                }
-            }
-        }
-        any_cell_filled
+    pub fn solve(&mut self) -> Result<(), SolverError> {
+        while self.use_pipeline() {}
+        let layout = self.notes_manager.get_layout();
+        validate_puzzle(layout, &self.puzzle)?;
+        validate_notes(layout, &self.puzzle, &self.notes_manager.get())?;
+        self.use_backtracking()
     }
 
-    fn set_hiden_ones(&mut self) -> bool {
-        let mut result: bool = false;
-        for value in 1..=GRID_SIZE {
-            result |= perform_for_all_sets(|coordinates| { self.set_hidden(coordinates, value) });
-        }
-        result
+    fn use_pipeline(&mut self) -> bool {
+        let uniqueness = self.uniqueness;
+        let notes_manager = &mut self.notes_manager;
+        let deductions = self.pipeline
+            .iter()
+            .filter(|technique| uniqueness || !technique.assumes_uniqueness())
+            .map(|technique| notes_manager.use_technique(technique))
+            .find(|deductions| !deductions.is_empty());
+        deductions.is_some_and(|deductions| self.record(deductions))
     }
 
-    fn set_hidden(&mut self, coordinates: &Coordinates, value: usize) -> bool {
-        self.notes_manager.get_hidden(coordinates, value).map_or(false, |(row, col)| {
-            self.set(row, col, value as u8);
-            true
-        })
+    fn use_backtracking(&mut self) -> Result<(), SolverError> {
+        if self.puzzle.iter().all(|row| !row.contains(&0)) {
+            return Ok(());
+        }
+        let layout = self.notes_manager.get_layout();
+        let solution = crate::solve::dancing_links::solve(layout, &self.puzzle)
+            .ok_or(SolverError::NoSolution)?;
+        self.guessed = true;
+        self.record(vec![Deduction::guess(&self.puzzle, &solution)]);
+        Ok(())
     }
 
-    fn set(&mut self, row: usize, col: usize, value: u8) {
-        self.puzzle[row][col] = value;
-        self.notes_manager.adjust(row, col, value);
+    fn record(&mut self, deductions: Vec<Deduction>) -> bool {
+        for deduction in deductions.iter() {
+            for ((row, col), value) in deduction.placements.iter() {
+                self.puzzle[*row][*col] = *value;
+            }
+        }
+        let progress = !deductions.is_empty();
+        if self.tracing {
+            self.trace.extend(deductions);
+        }
+        progress
     }
 }
 
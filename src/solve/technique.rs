@@ -0,0 +1,383 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use crate::common::grid_size::GRID_SIZE;
+use crate::solve::notes::Notes;
+use crate::solve::coordinates::Layout;
+use crate::solve::deduction::Deduction;
+use crate::solve::rating::get_difficulty;
+use crate::solve::fish::FISH_NAMES;
+use crate::solve::hidden_sets::{ NAKED_SETS, HIDDEN_SETS };
+
+const NAKED_SET_GROUPS: [&str; 3] = ["Naked Pairs", "Naked Triples", "Naked Quads"];
+const HIDDEN_SET_GROUPS: [&str; 3] = ["Hidden Pairs", "Hidden Triples", "Hidden Quads"];
+const FISH_GROUPS: [&str; 3] = ["X-Wings", "Swordfish", "Jellyfish"];
+const FINNED_FISH_GROUPS: [&str; 3] = ["Finned X-Wings", "Finned Swordfish", "Finned Jellyfish"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SetSize {
+    Pair = 2,
+    Triple = 3,
+    Quad = 4,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FishSize {
+    XWing = 2,
+    Swordfish = 3,
+    Jellyfish = 4,
+}
+
+pub trait Technique<const N: usize = GRID_SIZE> {
+    fn name(&self) -> &'static str;
+    fn difficulty(&self) -> f32;
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction>;
+
+    fn assumes_uniqueness(&self) -> bool {
+        false
+    }
+}
+
+pub struct NakedSingles;
+pub struct HiddenSingles;
+pub struct Cages;
+pub struct Lines;
+pub struct NakedSets(pub RangeInclusive<SetSize>);
+pub struct PointingSets;
+pub struct HiddenSets(pub RangeInclusive<SetSize>);
+pub struct Fish(pub FishSize);
+pub struct FinnedFish(pub FishSize);
+pub struct Uniqueness;
+pub struct Wings;
+pub struct ShortChains;
+pub struct Colouring;
+pub struct Als;
+pub struct Chains;
+
+impl<const N: usize> Technique<N> for NakedSingles {
+    fn name(&self) -> &'static str {
+        "Naked Singles"
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty("Naked Single")
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::singles::use_naked_singles(layout, notes)
+    }
+}
+
+impl<const N: usize> Technique<N> for HiddenSingles {
+    fn name(&self) -> &'static str {
+        "Hidden Singles"
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty("Hidden Single")
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::singles::use_hidden_singles(layout, notes)
+    }
+}
+
+impl<const N: usize> Technique<N> for Cages {
+    fn name(&self) -> &'static str {
+        "Killer Cages"
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty("Cage Combination")
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::cages::use_cages(layout, notes)
+    }
+}
+
+impl<const N: usize> Technique<N> for Lines {
+    fn name(&self) -> &'static str {
+        "Line Constraints"
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty("Thermometer")
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::lines::use_lines(layout, notes)
+    }
+}
+
+impl<const N: usize> Technique<N> for NakedSets {
+    fn name(&self) -> &'static str {
+        get_group_name(&self.0, "Naked Sets", &NAKED_SET_GROUPS)
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty(NAKED_SETS[*self.0.start() as usize - 2])
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::hidden_sets::use_naked_sets(layout, notes, get_sizes(&self.0))
+    }
+}
+
+impl<const N: usize> Technique<N> for PointingSets {
+    fn name(&self) -> &'static str {
+        "Pointing Sets"
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty("Pointing Set")
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::pointing_sets::use_pointing_sets(layout, notes)
+    }
+}
+
+impl<const N: usize> Technique<N> for HiddenSets {
+    fn name(&self) -> &'static str {
+        get_group_name(&self.0, "Hidden Sets", &HIDDEN_SET_GROUPS)
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty(HIDDEN_SETS[*self.0.start() as usize - 2])
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::hidden_sets::use_hidden_sets(layout, notes, get_sizes(&self.0))
+    }
+}
+
+impl<const N: usize> Technique<N> for Fish {
+    fn name(&self) -> &'static str {
+        FISH_GROUPS[self.0 as usize - 2]
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty(FISH_NAMES[0][self.0 as usize - 2])
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::fish::use_basic_fish(layout, notes, self.0 as usize)
+    }
+}
+
+impl<const N: usize> Technique<N> for FinnedFish {
+    fn name(&self) -> &'static str {
+        FINNED_FISH_GROUPS[self.0 as usize - 2]
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty(FISH_NAMES[1][self.0 as usize - 2])
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::fish::use_finned_fish(layout, notes, self.0 as usize)
+    }
+}
+
+impl<const N: usize> Technique<N> for Uniqueness {
+    fn name(&self) -> &'static str {
+        "Uniqueness"
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty("Unique Rectangle Type 1")
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::uniqueness::use_uniqueness(layout, notes)
+    }
+
+    fn assumes_uniqueness(&self) -> bool {
+        true
+    }
+}
+
+impl<const N: usize> Technique<N> for Wings {
+    fn name(&self) -> &'static str {
+        "Wings"
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty("XY-Wing")
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::wings::use_wings(layout, notes)
+    }
+}
+
+impl<const N: usize> Technique<N> for ShortChains {
+    fn name(&self) -> &'static str {
+        "Short Chains"
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty("Skyscraper")
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::short_chains::use_short_chains(layout, notes)
+    }
+}
+
+impl<const N: usize> Technique<N> for Colouring {
+    fn name(&self) -> &'static str {
+        "Colouring"
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty("Colour Trap")
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::colouring::use_colouring(layout, notes)
+    }
+}
+
+impl<const N: usize> Technique<N> for Als {
+    fn name(&self) -> &'static str {
+        "Almost Locked Sets"
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty("ALS-XZ")
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::als::use_als(layout, notes)
+    }
+}
+
+impl<const N: usize> Technique<N> for Chains {
+    fn name(&self) -> &'static str {
+        "Chains"
+    }
+
+    fn difficulty(&self) -> f32 {
+        get_difficulty("X-Chain")
+    }
+
+    fn apply(&self, layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+        crate::solve::chains::use_chains(layout, notes)
+    }
+}
+
+fn get_group_name(
+    sizes: &RangeInclusive<SetSize>,
+    name: &'static str,
+    groups: &[&'static str]
+) -> &'static str {
+    if sizes.start() == sizes.end() {
+        groups[*sizes.start() as usize - 2]
+    } else {
+        name
+    }
+}
+
+fn get_sizes(sizes: &RangeInclusive<SetSize>) -> RangeInclusive<usize> {
+    *sizes.start() as usize..=*sizes.end() as usize
+}
+
+pub struct Pipeline<const N: usize = GRID_SIZE> {
+    techniques: Vec<Box<dyn Technique<N>>>,
+}
+
+impl<const N: usize> Pipeline<N> {
+    pub fn new(techniques: Vec<Box<dyn Technique<N>>>) -> Self {
+        Pipeline { techniques }
+    }
+
+    pub fn beginner() -> Self {
+        Pipeline::new(vec![
+            Box::new(NakedSingles),
+            Box::new(HiddenSingles),
+            Box::new(NakedSets(SetSize::Pair..=SetSize::Quad)),
+            Box::new(PointingSets),
+            Box::new(HiddenSets(SetSize::Pair..=SetSize::Quad)),
+        ])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Technique<N>> {
+        self.techniques.iter().map(|technique| technique.as_ref())
+    }
+
+    pub fn get_difficulty(&self) -> f32 {
+        self.iter().map(|technique| technique.difficulty()).fold(0.0, f32::max)
+    }
+}
+
+impl<const N: usize> Default for Pipeline<N> {
+    fn default() -> Self {
+        Pipeline::new(vec![
+            Box::new(NakedSingles),
+            Box::new(HiddenSingles),
+            Box::new(Cages),
+            Box::new(Lines),
+            Box::new(PointingSets),
+            Box::new(NakedSets(SetSize::Pair..=SetSize::Pair)),
+            Box::new(Fish(FishSize::XWing)),
+            Box::new(HiddenSets(SetSize::Pair..=SetSize::Pair)),
+            Box::new(NakedSets(SetSize::Triple..=SetSize::Triple)),
+            Box::new(FinnedFish(FishSize::XWing)),
+            Box::new(Fish(FishSize::Swordfish)),
+            Box::new(HiddenSets(SetSize::Triple..=SetSize::Triple)),
+            Box::new(ShortChains),
+            Box::new(FinnedFish(FishSize::Swordfish)),
+            Box::new(Wings),
+            Box::new(Uniqueness),
+            Box::new(Colouring),
+            Box::new(NakedSets(SetSize::Quad..=SetSize::Quad)),
+            Box::new(Fish(FishSize::Jellyfish)),
+            Box::new(HiddenSets(SetSize::Quad..=SetSize::Quad)),
+            Box::new(FinnedFish(FishSize::Jellyfish)),
+            Box::new(Als),
+            Box::new(Chains),
+        ])
+    }
+}
+
+impl<const N: usize> fmt::Display for Pipeline<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.iter().map(|technique| technique.name()).collect();
+        write!(f, "{}", names.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_beginner() {
+        let pipeline: Pipeline = Pipeline::beginner();
+        let expected = "Naked Singles, Hidden Singles, Naked Sets, Pointing Sets, Hidden Sets";
+        assert_eq!(pipeline.to_string(), expected);
+        assert_eq!(pipeline.get_difficulty(), get_difficulty("Hidden Pair"));
+        assert!(pipeline.iter().all(|technique| !technique.assumes_uniqueness()));
+    }
+
+    #[test]
+    fn test_custom_order() {
+        let pipeline: Pipeline = Pipeline::new(vec![Box::new(Chains), Box::new(HiddenSingles)]);
+        assert_eq!(pipeline.to_string(), "Chains, Hidden Singles");
+        assert_eq!(Pipeline::<GRID_SIZE>::new(Vec::new()).get_difficulty(), 0.0);
+        let pipeline: Pipeline = Pipeline::default();
+        assert!(pipeline.iter().any(|technique| technique.assumes_uniqueness()));
+    }
+
+    #[test]
+    fn test_increasing_difficulty() {
+        let pipeline: Pipeline = Pipeline::default();
+        let difficulties: Vec<f32> = pipeline
+            .iter()
+            .skip(2)
+            .map(|technique| technique.difficulty())
+            .collect();
+        assert!(difficulties.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+}
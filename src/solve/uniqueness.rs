@@ -0,0 +1,323 @@
+use crate::solve::notes::*;
+use crate::solve::coordinates::*;
+use crate::solve::chains::Candidate;
+use crate::solve::fish::combinations;
+use crate::solve::deduction::Deduction;
+use crate::solve::candidate_set::CandidateSet;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UniquenessKind {
+    UniqueRectangle1,
+    UniqueRectangle2,
+    UniqueRectangle3,
+    UniqueRectangle4,
+    BugPlusOne,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Uniqueness {
+    pub kind: UniquenessKind,
+    pub cells: Vec<Point>,
+    pub eliminations: Vec<Candidate>,
+}
+
+impl UniquenessKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            UniquenessKind::UniqueRectangle1 => "Unique Rectangle Type 1",
+            UniquenessKind::UniqueRectangle2 => "Unique Rectangle Type 2",
+            UniquenessKind::UniqueRectangle3 => "Unique Rectangle Type 3",
+            UniquenessKind::UniqueRectangle4 => "Unique Rectangle Type 4",
+            UniquenessKind::BugPlusOne => "BUG+1",
+        }
+    }
+}
+
+impl Uniqueness {
+    pub fn clear<const N: usize>(&self, notes: &mut Notes<N>) -> bool {
+        let mut result = false;
+        for (cell, value) in self.eliminations.iter() {
+            result |= clear_value(notes, *value, &[*cell]);
+        }
+        result
+    }
+
+    pub fn apply<const N: usize>(&self, notes: &mut Notes<N>) -> Option<Deduction> {
+        Deduction::record(self.kind.name(), &self.cells, notes, |notes| self.clear(notes))
+    }
+}
+
+pub fn use_uniqueness<const N: usize>(layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+    let deductions: Vec<Deduction> = find_unique_rectangles(layout, notes)
+        .iter()
+        .filter_map(|rectangle| rectangle.apply(notes))
+        .collect();
+    if !deductions.is_empty() {
+        return deductions;
+    }
+    find_bug_plus_one(layout, notes).and_then(|bug| bug.apply(notes)).into_iter().collect()
+}
+
+pub fn find_unique_rectangles<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>
+) -> Vec<Uniqueness> {
+    if layout.has_chess_constraints() {
+        return Vec::new(); // swapping a pair can break a knight, king or consecutive relation
+    }
+    let mut found = Vec::new();
+    for top in 0..N - 1 {
+        for bottom in top + 1..N {
+            for left in 0..N - 1 {
+                for right in left + 1..N {
+                    let cells = [(top, left), (top, right), (bottom, left), (bottom, right)];
+                    let [top_left, top_right, bottom_left, bottom_right] =
+                        cells.map(|cell| layout.get_region(cell));
+                    let rows_paired = top_left == top_right && bottom_left == bottom_right;
+                    let cols_paired = top_left == bottom_left && top_right == bottom_right;
+                    if rows_paired == cols_paired {
+                        continue; // a deadly pattern has to span exactly two regions
+                    }
+                    if breaks_constraints(layout, &cells) {
+                        continue; // swapping the pair must not change any extra house, cage or line
+                    }
+                    found.extend(check_rectangle(layout, notes, &cells));
+                }
+            }
+        }
+    }
+    found
+}
+
+fn check_rectangle<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    cells: &[Point; 4]
+) -> Vec<Uniqueness> {
+    let mut found = Vec::new();
+    let common = cells
+        .iter()
+        .fold(CandidateSet::full(N), |common, (row, col)| common & notes[*row][*col]);
+    for (first, second) in get_pairs(common) {
+        let pair: CandidateSet = [first, second].into_iter().collect();
+        let (floor, roof): (Vec<Point>, Vec<Point>) = cells
+            .iter()
+            .partition(|(row, col)| notes[*row][*col] == pair);
+        let rectangle = match roof.len() {
+            1 => check_type_1(roof[0], [first, second]),
+            2 => {
+                check_type_2(layout, notes, pair, &roof)
+                    .or_else(|| check_type_3(layout, notes, pair, &roof))
+                    .or_else(|| check_type_4(layout, notes, [first, second], &roof))
+            }
+            _ => None,
+        };
+        found.extend(
+            rectangle.map(|(kind, eliminations)| {
+                let mut involved = floor.clone();
+                involved.extend(roof.iter());
+                Uniqueness { kind, cells: involved, eliminations }
+            })
+        );
+    }
+    found
+}
+
+fn check_type_1(roof: Point, pair: [usize; 2]) -> Option<(UniquenessKind, Vec<Candidate>)> {
+    Some((UniquenessKind::UniqueRectangle1, pair.iter().map(|value| (roof, *value)).collect()))
+}
+
+fn check_type_2<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    pair: CandidateSet,
+    roof: &[Point]
+) -> Option<(UniquenessKind, Vec<Candidate>)> {
+    let extra = notes[roof[0].0][roof[0].1] - pair;
+    let value = extra.get_single()?;
+    if notes[roof[1].0][roof[1].1] - pair != extra {
+        return None;
+    }
+    let eliminations: Vec<Candidate> = layout.get_peers(roof[0])
+        .into_iter()
+        .filter(|point| layout.sees(roof[1], *point) && notes[point.0][point.1].contains(value))
+        .map(|point| (point, value))
+        .collect();
+    if eliminations.is_empty() {
+        None
+    } else {
+        Some((UniquenessKind::UniqueRectangle2, eliminations))
+    }
+}
+
+fn check_type_3<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    pair: CandidateSet,
+    roof: &[Point]
+) -> Option<(UniquenessKind, Vec<Candidate>)> {
+    let extra = (notes[roof[0].0][roof[0].1] | notes[roof[1].0][roof[1].1]) - pair;
+    if extra.count() < 2 {
+        return None;
+    }
+    for house in get_shared_houses(layout, roof[0], roof[1]) {
+        let others: Vec<usize> = (0..N)
+            .filter(|index| !roof.contains(&house[*index]))
+            .filter(|index| !notes[house[*index].0][house[*index].1].is_empty())
+            .collect();
+        for size in 1..=3 {
+            for subset in combinations(&others, size) {
+                let note = subset
+                    .iter()
+                    .fold(extra, |note, index| note | notes[house[*index].0][house[*index].1]);
+                if note.count() != size + 1 {
+                    continue;
+                }
+                let eliminations = get_subset_eliminations(notes, &house, note, roof, &subset);
+                if !eliminations.is_empty() {
+                    return Some((UniquenessKind::UniqueRectangle3, eliminations));
+                }
+            }
+        }
+    }
+    None
+}
+
+fn get_subset_eliminations<const N: usize>(
+    notes: &Notes<N>,
+    house: &Coordinates<N>,
+    note: CandidateSet,
+    roof: &[Point],
+    subset: &[usize]
+) -> Vec<Candidate> {
+    let mut eliminations = Vec::new();
+    for (index, (row, col)) in house.iter().enumerate() {
+        if roof.contains(&(*row, *col)) || subset.contains(&index) {
+            continue;
+        }
+        for value in (notes[*row][*col] & note).iter() {
+            eliminations.push(((*row, *col), value));
+        }
+    }
+    eliminations
+}
+
+fn check_type_4<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    [first, second]: [usize; 2],
+    roof: &[Point]
+) -> Option<(UniquenessKind, Vec<Candidate>)> {
+    for house in get_shared_houses(layout, roof[0], roof[1]) {
+        for (locked, value) in [(first, second), (second, first)] {
+            let is_locked = house.iter().all(|(row, col)| {
+                roof.contains(&(*row, *col)) || !notes[*row][*col].contains(locked)
+            });
+            if is_locked {
+                let eliminations = roof.iter().map(|cell| (*cell, value)).collect();
+                return Some((UniquenessKind::UniqueRectangle4, eliminations));
+            }
+        }
+    }
+    None
+}
+
+pub fn find_bug_plus_one<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>
+) -> Option<Uniqueness> {
+    if !layout.get_cages().is_empty() || !layout.get_lines().is_empty()
+        || layout.has_chess_constraints()
+    {
+        return None; // cages, lines and chess relations can tell the two BUG solutions apart
+    }
+    let mut extra_cell = None;
+    for (row, notes_row) in notes.iter().enumerate() {
+        for (col, note) in notes_row.iter().enumerate() {
+            match note.count() {
+                0 | 2 => {}
+                3 if extra_cell.is_none() => {
+                    extra_cell = Some((row, col));
+                }
+                _ => {
+                    return None;
+                }
+            }
+        }
+    }
+    let (row, col) = extra_cell?;
+    let houses = [
+        layout.get_row_coordinates(row),
+        layout.get_col_coordinates(col),
+        layout.get_region_coordinates(layout.get_region((row, col))),
+    ];
+    let value = notes[row][col]
+        .iter()
+        .find(|value| houses.iter().all(|house| count_value(notes, house, *value) == 3))?;
+    let is_broken = layout.perform_for_all_sets(|house| {
+        (1..=N).any(|other| {
+            let expected = if other == value && house.contains(&(row, col)) { 3 } else { 2 };
+            let count = count_value(notes, house, other);
+            count != 0 && count != expected
+        })
+    });
+    if is_broken {
+        return None;
+    }
+    let eliminations = notes[row][col]
+        .iter()
+        .filter(|other| *other != value)
+        .map(|other| ((row, col), other))
+        .collect();
+    Some(Uniqueness { kind: UniquenessKind::BugPlusOne, cells: vec![(row, col)], eliminations })
+}
+
+fn breaks_constraints<const N: usize>(layout: &Layout<N>, corners: &[Point]) -> bool {
+    let extra_houses = layout.get_extra_houses().iter().map(|house| house.as_slice());
+    let cages = layout.get_cages().iter().map(|cage| cage.cells.as_slice());
+    let is_on_line = layout
+        .get_lines()
+        .iter()
+        .any(|line| corners.iter().any(|corner| line.cells.contains(corner)));
+    is_on_line || extra_houses.chain(cages).any(|cells| {
+        corners.iter().filter(|corner| cells.contains(corner)).count() % 2 == 1
+    })
+}
+
+fn count_value<const N: usize>(notes: &Notes<N>, house: &Coordinates<N>, value: usize) -> usize {
+    house.iter().filter(|(row, col)| notes[*row][*col].contains(value)).count()
+}
+
+fn get_shared_houses<const N: usize>(
+    layout: &Layout<N>,
+    first: Point,
+    second: Point
+) -> Vec<Coordinates<N>> {
+    let mut houses = Vec::new();
+    if first.0 == second.0 {
+        houses.push(layout.get_row_coordinates(first.0));
+    }
+    if first.1 == second.1 {
+        houses.push(layout.get_col_coordinates(first.1));
+    }
+    if layout.get_region(first) == layout.get_region(second) {
+        houses.push(layout.get_region_coordinates(layout.get_region(first)));
+    }
+    houses
+}
+
+fn get_pairs(note: CandidateSet) -> Vec<(usize, usize)> {
+    let values: Vec<usize> = note.iter().collect();
+    let mut pairs = Vec::new();
+    for (index, first) in values.iter().enumerate() {
+        for second in values[index + 1..].iter() {
+            pairs.push((*first, *second));
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    include!("ut/test_uniqueness.rs");
+}
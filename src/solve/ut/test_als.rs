@@ -0,0 +1,103 @@
+use super::*;
+use crate::common::grid_size::GRID_SIZE;
+
+fn notes_with(cells: &[(Point, u32)]) -> Notes {
+    let mut notes: Notes = [[CandidateSet::EMPTY; GRID_SIZE]; GRID_SIZE];
+    for ((row, col), note) in cells {
+        notes[*row][*col] = CandidateSet::from_bits(*note);
+    }
+    notes
+}
+
+#[test]
+fn test_almost_locked_sets() {
+    let notes = notes_with(&[
+        ((0, 0), 0b000_000_011),
+        ((0, 1), 0b000_000_111),
+        ((0, 2), 0b000_011_000),
+    ]);
+    let expected = vec![
+        Als { cells: vec![(0, 0)], note: CandidateSet::from_bits(0b000_000_011) },
+        Als { cells: vec![(0, 2)], note: CandidateSet::from_bits(0b000_011_000) },
+        Als { cells: vec![(0, 0), (0, 1)], note: CandidateSet::from_bits(0b000_000_111) },
+    ];
+    assert_eq!(get_almost_locked_sets(&Layout::default(), &notes), expected);
+}
+
+#[test]
+fn test_als_xz() {
+    let mut notes = notes_with(&[
+        ((0, 0), 0b000_000_011),
+        ((4, 0), 0b000_000_101),
+        ((4, 4), 0b000_000_110),
+        ((0, 4), 0b001_000_010),
+        ((4, 8), 0b110_000_000),
+    ]);
+    let pattern = AlsPattern {
+        kind: AlsKind::AlsXz,
+        sets: vec![vec![(0, 0)], vec![(4, 0), (4, 4)]],
+        eliminations: vec![((0, 4), 2)],
+    };
+    assert!(find_als_xz(&Layout::default(), &notes).contains(&pattern));
+    assert!(!use_als(&Layout::default(), &mut notes).is_empty());
+    assert_eq!(notes[0][4].bits(), 0b001_000_000);
+}
+
+#[test]
+fn test_doubly_linked_als_xz() {
+    let mut notes = notes_with(&[
+        ((0, 0), 0b000_000_011),
+        ((0, 3), 0b000_000_101),
+        ((0, 4), 0b000_000_110),
+        ((0, 8), 0b100_000_001),
+        ((1, 4), 0b100_000_100),
+    ]);
+    let pattern = AlsPattern {
+        kind: AlsKind::AlsXz,
+        sets: vec![vec![(0, 0)], vec![(0, 3), (0, 4)]],
+        eliminations: vec![((0, 8), 1), ((1, 4), 3)],
+    };
+    assert!(find_als_xz(&Layout::default(), &notes).contains(&pattern));
+    assert!(!use_als(&Layout::default(), &mut notes).is_empty());
+    assert_eq!(notes[0][8].bits(), 0b100_000_000);
+    assert_eq!(notes[1][4].bits(), 0b100_000_000);
+}
+
+#[test]
+fn test_als_xy_wing() {
+    let notes = notes_with(&[
+        ((0, 0), 0b000_000_101),
+        ((0, 4), 0b000_000_011),
+        ((4, 4), 0b000_000_110),
+        ((4, 0), 0b000_010_100),
+    ]);
+    let pattern = AlsPattern {
+        kind: AlsKind::AlsXyWing,
+        sets: vec![vec![(0, 0)], vec![(0, 4)], vec![(4, 4)]],
+        eliminations: vec![((4, 0), 3)],
+    };
+    assert!(find_als_xy_wings(&Layout::default(), &notes).contains(&pattern));
+}
+
+#[test]
+fn test_sue_de_coq() {
+    let mut notes = notes_with(&[
+        ((0, 0), 0b000_000_111),
+        ((0, 1), 0b000_001_011),
+        ((0, 5), 0b000_000_011),
+        ((1, 0), 0b000_001_100),
+        ((0, 7), 0b000_010_001),
+        ((2, 2), 0b000_101_000),
+    ]);
+    let pattern = AlsPattern {
+        kind: AlsKind::SueDeCoq,
+        sets: vec![vec![(0, 0), (0, 1)], vec![(0, 5)], vec![(1, 0)]],
+        eliminations: vec![((0, 7), 1), ((2, 2), 4)],
+    };
+    assert!(find_sue_de_coq(&Layout::default(), &notes).contains(&pattern));
+    for pattern in find_sue_de_coq(&Layout::default(), &notes) {
+        pattern.clear(&mut notes);
+    }
+    assert_eq!(notes[0][7].bits(), 0b000_010_000);
+    assert_eq!(notes[2][2].bits(), 0b000_100_000);
+}
@@ -0,0 +1,105 @@
+use super::*;
+use crate::common::grid_size::GRID_SIZE;
+
+fn cage(cells: &[Point], sum: usize) -> Cage {
+    Cage { cells: cells.to_vec(), sum }
+}
+
+fn full_notes() -> Notes {
+    [[CandidateSet::full(GRID_SIZE); GRID_SIZE]; GRID_SIZE]
+}
+
+#[test]
+fn test_fits() {
+    let notes = full_notes();
+    let mut puzzle = [[0; GRID_SIZE]; GRID_SIZE];
+    assert!(cage(&[(0, 0), (0, 1)], 3).fits(&puzzle, &notes));
+    assert!(cage(&[(0, 0), (0, 1)], 17).fits(&puzzle, &notes));
+    assert!(!cage(&[(0, 0), (0, 1)], 2).fits(&puzzle, &notes));
+    assert!(!cage(&[(0, 0), (0, 1)], 18).fits(&puzzle, &notes));
+    puzzle[0][0] = 2;
+    assert!(cage(&[(0, 0), (0, 1)], 3).fits(&puzzle, &notes));
+    assert!(!cage(&[(0, 0), (0, 1)], 4).fits(&puzzle, &notes));
+    puzzle[1][1] = 2;
+    assert!(!cage(&[(0, 0), (1, 1)], 4).fits(&puzzle, &notes));
+}
+
+#[test]
+fn test_fits_with_notes() {
+    let mut notes = full_notes();
+    notes[0][0] = CandidateSet::from_bits(0b110_000_000);
+    notes[0][1] = CandidateSet::from_bits(0b110_000_000);
+    let puzzle = [[0; GRID_SIZE]; GRID_SIZE];
+    assert!(cage(&[(0, 0), (0, 1), (0, 2)], 24).fits(&puzzle, &notes));
+    assert!(!cage(&[(0, 0), (0, 1), (0, 2)], 25).fits(&puzzle, &notes));
+}
+
+#[test]
+fn test_without_solved() {
+    let mut puzzle = [[0; GRID_SIZE]; GRID_SIZE];
+    puzzle[0][1] = 4;
+    let reduced = cage(&[(0, 0), (0, 1), (0, 2)], 15).without_solved(&puzzle);
+    assert_eq!(reduced, cage(&[(0, 0), (0, 2)], 11));
+}
+
+#[test]
+fn test_cage_combinations() {
+    let cages = vec![cage(&[(0, 0), (0, 1)], 3), cage(&[(4, 4), (5, 4)], 17)];
+    let layout = Layout::default().with_cages(cages).unwrap();
+    let mut notes = full_notes();
+    let deductions = use_cage_combinations(&layout, &mut notes);
+    assert_eq!(deductions.len(), 2);
+    assert!(deductions.iter().all(|deduction| deduction.technique == CAGE_COMBINATION));
+    assert_eq!(notes[0][0].bits(), 0b000_000_011);
+    assert_eq!(notes[0][1].bits(), 0b000_000_011);
+    assert_eq!(notes[4][4].bits(), 0b110_000_000);
+    assert_eq!(notes[5][4].bits(), 0b110_000_000);
+    assert!(use_cage_combinations(&layout, &mut notes).is_empty());
+}
+
+#[test]
+fn test_innies() {
+    let layout = Layout::default().with_cages(vec![
+        cage(&[(0, 0), (0, 1)], 3),
+        cage(&[(0, 2), (0, 3)], 7),
+        cage(&[(0, 4), (0, 5)], 11),
+        cage(&[(0, 6), (0, 7)], 15),
+    ]).unwrap();
+    let mut notes = full_notes();
+    let deductions = use_innies_and_outies(&layout, &mut notes);
+    assert_eq!(deductions.len(), 1);
+    assert_eq!(deductions[0].technique, INNIES_AND_OUTIES);
+    assert_eq!(notes[0][8].bits(), 0b100_000_000);
+}
+
+#[test]
+fn test_outies() {
+    let layout = Layout::default().with_cages(vec![
+        cage(&[(8, 0), (8, 1)], 3),
+        cage(&[(8, 2), (8, 3)], 7),
+        cage(&[(8, 4), (8, 5)], 11),
+        cage(&[(7, 8), (8, 6), (8, 7), (8, 8)], 27),
+    ]).unwrap();
+    let mut notes = full_notes();
+    assert!(!use_innies_and_outies(&layout, &mut notes).is_empty());
+    assert_eq!(notes[7][8].bits(), 0b000_000_100);
+    assert_eq!(notes[8][6].bits(), 0b111_000_000);
+    assert_eq!(notes[8][8].bits(), 0b111_000_000);
+}
+
+#[test]
+fn test_cage_naked_sets() {
+    let cages = vec![cage(&[(0, 0), (0, 3), (1, 3)], 12)];
+    let layout = Layout::default().with_cages(cages).unwrap();
+    let mut notes = full_notes();
+    notes[0][0] = CandidateSet::from_bits(0b000_000_011);
+    notes[1][3] = CandidateSet::from_bits(0b000_000_011);
+    let deductions = use_cage_naked_sets(&layout, &mut notes);
+    assert_eq!(deductions.len(), 1);
+    assert_eq!(deductions[0].technique, NAKED_SETS[0]);
+    for (row, col) in [(0, 3), (0, 4), (1, 0), (1, 2)] {
+        assert_eq!(notes[row][col].bits(), 0b111_111_100);
+    }
+    assert_eq!(notes[0][0].bits(), 0b000_000_011);
+    assert_eq!(notes[2][2].bits(), 0b111_111_111);
+}
@@ -0,0 +1,111 @@
+use super::*;
+use crate::common::grid_size::GRID_SIZE;
+use crate::solve::candidate_set::CandidateSet;
+
+const NOTES_WRAP: [[u32; GRID_SIZE]; GRID_SIZE] = [
+    [0b000_000_001, 0, 0, 0, 0b000_000_001, 0, 0, 0, 0],
+    [0, 0, 0b000_000_001, 0, 0, 0, 0, 0, 0],
+    [0, 0b000_000_001, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0b000_000_001, 0, 0, 0b000_000_001, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+];
+
+const NOTES_TRAP: [[u32; GRID_SIZE]; GRID_SIZE] = [
+    [0b000_000_001, 0, 0, 0, 0b000_000_001, 0, 0, 0, 0],
+    [0, 0b000_000_001, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0b000_000_001, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0b000_000_001, 0, 0, 0b000_000_001, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0b000_000_001, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+];
+
+const NOTES_MULTI: [[u32; GRID_SIZE]; GRID_SIZE] = [
+    [0b000_000_001, 0, 0, 0, 0, 0, 0b000_000_001, 0, 0],
+    [0, 0, 0b000_000_001, 0, 0, 0, 0, 0b000_000_001, 0],
+    [0, 0b000_000_001, 0, 0, 0b000_000_001, 0, 0, 0, 0b000_000_001],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0],
+];
+
+fn check_notes(notes: &Notes, expected: &Notes) {
+    for (row, row_pos) in notes.iter().enumerate() {
+        for (col, note) in row_pos.iter().enumerate() {
+            assert!(
+                &note.eq(&expected[row][col]),
+                "Wrong note on row:{}, col:{}, actual:{}, expected:{}",
+                row,
+                col,
+                &note,
+                &expected[row][col]
+            );
+        }
+    }
+}
+
+#[test]
+fn test_clusters() {
+    let layout = Layout::default();
+    let clusters = get_clusters(&get_strong_links(&layout, &from_bits(&NOTES_WRAP), 1));
+    assert_eq!(clusters, vec![[vec![(0, 0), (4, 4), (2, 1)], vec![(0, 4), (4, 1)]]]);
+    let clusters = get_clusters(&get_strong_links(&layout, &from_bits(&NOTES_MULTI), 1));
+    assert_eq!(clusters, vec![[vec![(0, 0)], vec![(0, 6)]], [vec![(1, 2)], vec![(1, 7)]]]);
+    assert!(get_clusters(&get_strong_links(&layout, &from_bits(&NOTES_MULTI), 2)).is_empty());
+}
+
+#[test]
+fn test_colour_wrap() {
+    let colourings = find_colourings(&Layout::default(), &from_bits(&NOTES_WRAP));
+    assert_eq!(colourings.len(), 1);
+    assert_eq!(colourings[0].kind, ColouringKind::ColourWrap);
+    assert_eq!(colourings[0].eliminations, vec![(0, 0), (4, 4), (2, 1)]);
+
+    let mut notes = from_bits(&NOTES_WRAP);
+    let mut expected = from_bits(&NOTES_WRAP);
+    expected[0][0] = CandidateSet::EMPTY;
+    expected[4][4] = CandidateSet::EMPTY;
+    expected[2][1] = CandidateSet::EMPTY;
+    assert!(!use_colouring(&Layout::default(), &mut notes).is_empty());
+    check_notes(&notes, &expected);
+}
+
+#[test]
+fn test_colour_trap() {
+    let colourings = find_colourings(&Layout::default(), &from_bits(&NOTES_TRAP));
+    assert_eq!(colourings.len(), 1);
+    assert_eq!(colourings[0].kind, ColouringKind::ColourTrap);
+    assert_eq!(colourings[0].eliminations, vec![(1, 1)]);
+
+    let mut notes = from_bits(&NOTES_TRAP);
+    let mut expected = from_bits(&NOTES_TRAP);
+    expected[1][1] = CandidateSet::EMPTY;
+    assert!(!use_colouring(&Layout::default(), &mut notes).is_empty());
+    check_notes(&notes, &expected);
+    assert!(use_colouring(&Layout::default(), &mut notes).is_empty());
+}
+
+#[test]
+fn test_multi_colouring() {
+    let colourings = find_colourings(&Layout::default(), &from_bits(&NOTES_MULTI));
+    assert_eq!(colourings.len(), 2);
+    assert!(colourings.iter().all(|colouring| colouring.kind == ColouringKind::MultiColouring));
+    assert_eq!(colourings[0].eliminations, vec![(2, 8)]);
+    assert_eq!(colourings[1].eliminations, vec![(2, 1)]);
+
+    let mut notes = from_bits(&NOTES_MULTI);
+    let mut expected = from_bits(&NOTES_MULTI);
+    expected[2][1] = CandidateSet::EMPTY;
+    expected[2][8] = CandidateSet::EMPTY;
+    assert!(!use_colouring(&Layout::default(), &mut notes).is_empty());
+    check_notes(&notes, &expected);
+}
@@ -0,0 +1,95 @@
+use super::*;
+use crate::common::grid_size::GRID_SIZE;
+
+fn full_notes() -> Notes {
+    [[CandidateSet::full(GRID_SIZE); GRID_SIZE]; GRID_SIZE]
+}
+
+fn restrict(line: Line, notes: &mut Notes) -> Vec<Deduction> {
+    use_lines(&Layout::default().with_lines(vec![line]).unwrap(), notes)
+}
+
+#[test]
+fn test_thermometer() {
+    let mut notes = full_notes();
+    notes[0][2] = CandidateSet::from_bits(0b000_110_000);
+    let line = Line::new(LineKind::Thermometer, vec![(0, 0), (0, 1), (0, 2), (0, 3)]);
+    let deductions = restrict(line, &mut notes);
+    assert_eq!(deductions.len(), 1);
+    assert_eq!(deductions[0].technique, THERMOMETER);
+    assert_eq!(notes[0][0].bits(), 0b000_001_111);
+    assert_eq!(notes[0][1].bits(), 0b000_011_110);
+    assert_eq!(notes[0][2].bits(), 0b000_110_000);
+    assert_eq!(notes[0][3].bits(), 0b111_100_000);
+}
+
+#[test]
+fn test_arrow() {
+    let mut notes = full_notes();
+    notes[0][0] = CandidateSet::from_bits(0b000_001_100);
+    let line = Line::new(LineKind::Arrow, vec![(0, 0), (1, 0), (2, 0)]);
+    assert!(!restrict(line.clone(), &mut notes).is_empty());
+    assert_eq!(notes[0][0].bits(), 0b000_001_100);
+    assert_eq!(notes[1][0].bits(), 0b000_000_111);
+    assert_eq!(notes[2][0].bits(), 0b000_000_111);
+    assert!(restrict(line, &mut notes).is_empty());
+    let mut notes = full_notes();
+    let line = Line::new(LineKind::Arrow, vec![(0, 0), (3, 3), (4, 4), (5, 5)]);
+    restrict(line, &mut notes);
+    assert_eq!(notes[0][0].bits(), 0b111_100_000);
+    assert_eq!(notes[3][3].bits(), 0b000_111_111);
+}
+
+#[test]
+fn test_palindrome() {
+    let mut notes = full_notes();
+    notes[0][0] = CandidateSet::from_bits(0b000_000_011);
+    notes[2][2] = CandidateSet::from_bits(0b000_000_110);
+    let line = Line::new(LineKind::Palindrome, vec![(0, 0), (1, 1), (2, 2)]);
+    let deductions = restrict(line, &mut notes);
+    assert_eq!(deductions[0].technique, PALINDROME);
+    assert_eq!(notes[0][0].bits(), 0b000_000_010);
+    assert_eq!(notes[2][2].bits(), 0b000_000_010);
+    assert_eq!(notes[1][1].bits(), 0b111_111_111);
+}
+
+#[test]
+fn test_whispers() {
+    let mut notes = full_notes();
+    let line = Line::new(LineKind::Whispers, vec![(0, 0), (0, 1), (0, 2)]);
+    let deductions = restrict(line.clone(), &mut notes);
+    assert_eq!(deductions[0].technique, GERMAN_WHISPERS);
+    assert_eq!(notes[0][1].bits(), 0b111_101_111);
+    notes[0][1] = CandidateSet::from_bits(0b000_000_100);
+    restrict(line, &mut notes);
+    assert_eq!(notes[0][0].bits(), 0b110_000_000);
+    assert_eq!(notes[0][2].bits(), 0b110_000_000);
+}
+
+#[test]
+fn test_solved_cells() {
+    let mut puzzle = [[0; GRID_SIZE]; GRID_SIZE];
+    puzzle[0][1] = 3;
+    let mut notes = full_notes();
+    notes[0][1] = CandidateSet::EMPTY;
+    let line = Line::new(LineKind::Thermometer, vec![(0, 0), (0, 1), (0, 2)]);
+    assert!(line.fits(&Layout::default(), &puzzle, &notes));
+    let layout = Layout::default().with_lines(vec![line]).unwrap().without_solved(&puzzle);
+    assert!(!use_lines(&layout, &mut notes).is_empty());
+    assert_eq!(notes[0][0].bits(), 0b000_000_011);
+    assert_eq!(notes[0][1].bits(), 0);
+    assert_eq!(notes[0][2].bits(), 0b111_111_000);
+    puzzle[0][0] = 4;
+    assert!(!layout.get_lines()[0].fits(&layout, &puzzle, &notes));
+    puzzle[0][0] = 1;
+    puzzle[0][2] = 5;
+    assert!(layout.without_solved(&puzzle).get_lines().is_empty());
+}
+
+#[test]
+fn test_serialisation() {
+    let line = Line::new(LineKind::Arrow, vec![(0, 0), (0, 1)]);
+    let json = serde_json::to_string(&line).unwrap();
+    assert_eq!(json, r#"{"kind":"Arrow","cells":[[0,0],[0,1]]}"#);
+    assert_eq!(serde_json::from_str::<Line>(&json).unwrap(), line);
+}
@@ -1,4 +1,7 @@
 use super::*;
+use crate::common::grid_size::GRID_SIZE;
+use crate::solve::notes::from_bits;
+use crate::solve::technique::{ NakedSets, SetSize };
 
 const PUZZLE1: Puzzle = [
     [5, 3, 0, 0, 7, 0, 0, 0, 0],
@@ -12,7 +15,7 @@ const PUZZLE1: Puzzle = [
     [0, 0, 0, 0, 8, 0, 0, 7, 9],
 ];
 
-const NOTES1: Notes = [
+const NOTES1: [[u32; GRID_SIZE]; GRID_SIZE] = [
     [
         0, 0, 0b000_001_011, 0b000_100_010, 0, 0b010_101_010, 0b110_001_001, 0b100_001_011,
         0b010_001_010,
@@ -47,7 +50,7 @@ fn check_notes(notes: &Notes, expected: &Notes) {
         for (col, note) in row_pos.iter().enumerate() {
             assert!(
                 &note.eq(&expected[row][col]),
-                "Wrong note on row:{}, col:{}, actual:{:b}, expected:{:b}",
+                "Wrong note on row:{}, col:{}, actual:{}, expected:{}",
                 row,
                 col,
                 &note,
@@ -60,8 +63,24 @@ fn check_notes(notes: &Notes, expected: &Notes) {
 #[test]
 fn test_notes() {
     let mut sut = NotesManager::new(PUZZLE1);
-    sut.fill();
-    check_notes(&sut.get(), &NOTES1);
+    sut.fill().unwrap();
+    check_notes(&sut.get(), &from_bits(&NOTES1));
+}
+
+#[test]
+fn test_chess_notes() {
+    let mut puzzle = [[0; GRID_SIZE]; GRID_SIZE];
+    puzzle[3][3] = 5;
+    let layout = Layout::default().with_anti_knight().with_anti_king().with_non_consecutive();
+    let mut sut = NotesManager::with_layout(layout, puzzle);
+    sut.fill().unwrap();
+    let notes = sut.get();
+    assert_eq!(notes[1][2].bits(), 0b111_101_111);
+    assert_eq!(notes[2][2].bits(), 0b111_101_111);
+    assert_eq!(notes[2][3].bits(), 0b111_000_111);
+    assert_eq!(notes[3][2].bits(), 0b111_000_111);
+    assert_eq!(notes[4][4].bits(), 0b111_101_111);
+    assert_eq!(notes[0][0].bits(), 0b111_111_111);
 }
 
 fn prepare_obvious_pair() -> NotesManager {
@@ -77,7 +96,7 @@ fn prepare_obvious_pair() -> NotesManager {
         [0, 5, 8, 0, 0, 0, 0, 3, 0],
     ];
 
-    let notes: Notes = [
+    let notes: [[u32; GRID_SIZE]; GRID_SIZE] = [
         [
             0b110_001_011, 0, 0b100_001_011, 0b000_001_010, 0b100_000_110, 0b100_001_110, 0, 0,
             0b110_000_101,
@@ -117,8 +136,8 @@ fn prepare_obvious_pair() -> NotesManager {
     ];
 
     let mut sut = NotesManager::new(puzzle);
-    sut.fill();
-    check_notes(&sut.get(), &notes);
+    sut.fill().unwrap();
+    check_notes(&sut.get(), &from_bits(&notes));
     sut
 }
 
@@ -126,53 +145,56 @@ fn prepare_obvious_pair() -> NotesManager {
 fn test_obvious_pairs() {
     let mut sut = prepare_obvious_pair();
 
-    let notes: Notes = [
+    let notes: [[u32; GRID_SIZE]; GRID_SIZE] = [
         [
             0b110_001_011, 0, 0b100_001_011, 0b000_001_010, 0b100_000_110, 0b100_001_110, 0, 0,
             0b110_000_101,
         ],
         [
-            0, 0b110_001_011, 0b100_011_011, 0, 0b100_010_010, 0b100_001_010, 0b010_001_001, 0b110_001_011,
-            0,
+            0, 0b110_001_011, 0b100_011_011, 0, 0b000_010_000, 0b100_001_010, 0b010_001_001,
+            0b110_001_011, 0,
         ],
         [
-            0b100_011_010, 0b100_001_010, 0, 0b000_111_010, 0, 0, 0b000_001_100, 0b100_001_010,
+            0b000_010_000, 0b100_001_010, 0, 0b000_110_000, 0, 0, 0b000_001_100, 0b100_001_010,
             0b100_000_100,
         ],
         [
-            0b010_011_001, 0b010_001_001, 0, 0, 0b011_010_100, 0b001_001_100, 0b010_001_101, 0b010_011_001,
-            0,
+            0b010_011_001, 0b010_001_001, 0, 0, 0b011_010_100, 0b001_001_100, 0b010_001_101,
+            0b010_011_001, 0,
         ],
         [
-            0b110_011_011, 0b110_001_011, 0, 0b010_111_010, 0b010_110_010, 0b000_101_010, 0, 0b010_111_001,
-            0b010_010_001,
+            0b110_011_011, 0b110_001_011, 0, 0b010_111_010, 0b010_110_010, 0b000_101_010, 0,
+            0b010_111_001, 0b010_010_001,
         ],
         [
             0, 0b010_001_010, 0b000_011_010, 0b010_111_010, 0b010_110_110, 0, 0, 0b010_111_000,
             0b010_010_100,
         ],
         [
-            0b100_000_001, 0b001_000_000, 0b100_000_001, 0, 0, 0b001_100_000, 0, 0b010_110_000,
+            0b100_000_001, 0b001_000_000, 0b100_000_001, 0, 0, 0b001_100_000, 0, 0b010_010_000,
             0b011_010_000,
         ],
         [
-            0, 0b000_000_100, 0b000_000_010, 0b010_000_001, 0b001_000_000, 0, 0b010_000_001, 0b100_000_000,
-            0,
+            0, 0b001_000_100, 0b000_000_010, 0b010_000_011, 0b101_000_000, 0, 0b010_000_001,
+            0b100_000_000, 0,
         ],
         [
-            0b000_001_010, 0, 0, 0b000_100_011, 0b101_100_010, 0b101_100_010, 0b000_100_001, 0,
-            0b101_000_001,
+            0b000_001_010, 0, 0, 0b000_100_011, 0b101_100_010, 0b101_100_010, 0b000_100_000, 0,
+            0b001_000_001,
         ],
     ];
 
-    sut.set_obvious_pairs();
-    check_notes(&sut.get(), &notes);
+    sut.use_technique(&NakedSets(SetSize::Pair..=SetSize::Quad));
+    check_notes(&sut.get(), &from_bits(&notes));
 }
 
 #[test]
 fn test_obvious_pairs_helpers() {
-    let mut sut = prepare_obvious_pair();
-    let mut notes: Notes = [
+    use crate::solve::hidden_sets::clear_naked_set;
+    let mut actual = prepare_obvious_pair().get();
+    let members = CandidateSet::from_bits(0b101);
+    let values = CandidateSet::from_bits(0b100_000_001);
+    let mut notes: [[u32; GRID_SIZE]; GRID_SIZE] = [
         [
             0b110_001_011, 0, 0b100_001_011, 0b000_001_010, 0b100_000_110, 0b100_001_110, 0, 0,
             0b110_000_101,
@@ -211,8 +233,9 @@ fn test_obvious_pairs_helpers() {
         ],
     ];
 
-    sut.clear_notes_obvious_set_based(&get_row_coordinates(6), 0b100_000_001);
-    check_notes(&sut.get(), &notes);
+    let layout = Layout::default();
+    clear_naked_set(&mut actual, &layout.get_row_coordinates(6), members, values);
+    check_notes(&actual, &from_bits(&notes));
     notes[7] = [
         0, 0b001_000_110, 0b000_000_010, 0b010_000_011, 0b111_000_010, 0, 0b010_000_001, 0b110_000_001,
         0,
@@ -221,7 +244,8 @@ fn test_obvious_pairs_helpers() {
         0b000_001_010, 0, 0, 0b000_100_011, 0b101_100_010, 0b101_100_010, 0b000_100_001, 0,
         0b101_000_001,
     ];
-    assert!(sut.clear_notes_obvious_set_based(&get_square_coordinates((2, 0)), 0b100_000_001));
-    check_notes(&sut.get(), &notes);
-    assert!(!sut.clear_notes_obvious_set_based(&get_square_coordinates((2, 0)), 0b100_000_001));
+    let region = layout.get_region_coordinates(2);
+    assert!(clear_naked_set(&mut actual, &region, members, values));
+    check_notes(&actual, &from_bits(&notes));
+    assert!(!clear_naked_set(&mut actual, &region, members, values));
 }
@@ -1,4 +1,8 @@
 use super::*;
+use crate::solve::rating::*;
+use crate::solve::cages::Cage;
+use crate::solve::lines::*;
+use crate::solve::coordinates::Point;
 
 const PUZZLE1: Puzzle = [
     [5, 3, 0, 0, 7, 0, 0, 0, 0],
@@ -39,15 +43,25 @@ fn check_solution(input: &Puzzle, expected: &Puzzle) {
     }
 }
 
-fn test_solve(puzzle: &Puzzle, expected: &Puzzle) {
-    let mut sut = Solver::new(*puzzle);
-    sut.solve();
-    check_solution(&sut.get_solution(), &expected);
+fn test_solve(puzzle: &Puzzle, expected: &Puzzle, tier: Tier) {
+    assert_eq!(rate(&Layout::default(), puzzle).unwrap().tier, tier);
+    let oracle = crate::solve::dancing_links::solve(&Layout::default(), puzzle).unwrap();
+    check_solution(&oracle, expected);
+    let mut sut = Solver::new(*puzzle).unwrap();
+    sut.solve().unwrap();
+    check_solution(&sut.get_solution(), expected);
 }
 
 #[test]
 fn test_obvious_puzzle() {
-    test_solve(&PUZZLE1, &SOLUTION1);
+    test_solve(&PUZZLE1, &SOLUTION1, Tier::Easy);
+}
+
+#[test]
+fn test_no_guessing() {
+    let mut sut = Solver::new(PUZZLE1).unwrap();
+    sut.solve().unwrap();
+    assert!(!sut.needed_guessing());
 }
 
 #[test]
@@ -76,7 +90,7 @@ fn test_obvious_puzzle_2() {
         [1, 2, 3, 9, 7, 6, 8, 4, 5],
     ];
 
-    test_solve(&puzzle2, &solution2);
+    test_solve(&puzzle2, &solution2, Tier::Easy);
 }
 
 #[test]
@@ -105,7 +119,7 @@ fn test_easy_puzzle() {
         [7, 2, 1, 9, 6, 3, 5, 8, 4],
     ];
 
-    test_solve(&puzzle, &solution);
+    test_solve(&puzzle, &solution, Tier::Easy);
 }
 
 #[test]
@@ -134,10 +148,9 @@ fn test_medium_puzzle() {
         [3, 8, 4, 7, 6, 9, 2, 1, 5],
     ];
 
-    test_solve(&puzzle, &solution);
+    test_solve(&puzzle, &solution, Tier::Medium);
 }
 
-// #[ignore]
 #[test]
 fn test_hard_puzzle() {
     let puzzle: Puzzle = [
@@ -152,32 +165,19 @@ fn test_hard_puzzle() {
         [0, 0, 6, 0, 0, 0, 0, 4, 7],
     ];
 
-    //TODO: solve
     let solution: Puzzle = [
-        [3, 1, 4, 0, 0, 0, 2, 0, 0],
-        [0, 0, 2, 0, 0, 0, 1, 0, 9],
-        [0, 0, 8, 1, 6, 2, 4, 0, 3],
-        [0, 8, 9, 4, 7, 0, 5, 0, 0],
-        [0, 0, 7, 0, 0, 0, 6, 0, 0],
-        [0, 0, 3, 0, 8, 1, 7, 9, 0],
-        [7, 0, 1, 8, 3, 0, 9, 0, 0],
-        [8, 0, 5, 0, 0, 0, 3, 0, 0],
-        [0, 3, 6, 0, 1, 0, 8, 4, 7],
+        [3, 1, 4, 7, 5, 9, 2, 8, 6],
+        [6, 7, 2, 3, 4, 8, 1, 5, 9],
+        [5, 9, 8, 1, 6, 2, 4, 7, 3],
+        [1, 8, 9, 4, 7, 6, 5, 3, 2],
+        [4, 5, 7, 9, 2, 3, 6, 1, 8],
+        [2, 6, 3, 5, 8, 1, 7, 9, 4],
+        [7, 2, 1, 8, 3, 4, 9, 6, 5],
+        [8, 4, 5, 6, 9, 7, 3, 2, 1],
+        [9, 3, 6, 2, 1, 5, 8, 4, 7],
     ];
 
-    // let solution: Puzzle = [
-    //     [3, 1, 4, 7, 5, 9, 2, 8, 6],
-    //     [6, 7, 2, 3, 4, 8, 1, 5, 9],
-    //     [5, 9, 8, 1, 6, 2, 4, 7, 3],
-    //     [1, 8, 9, 4, 7, 6, 5, 2, 3],
-    //     [4, 5, 7, 9, 2, 3, 6, 1, 8],
-    //     [2, 6, 3, 5, 8, 1, 7, 9, 4],
-    //     [7, 2, 1, 8, 3, 4, 9, 6, 5],
-    //     [8, 4, 5, 6, 9, 7, 3, 2, 1],
-    //     [9, 3, 6, 2, 1, 5, 8, 4, 7],
-    // ];
-
-    test_solve(&puzzle, &solution);
+    test_solve(&puzzle, &solution, Tier::Hard);
 }
 
 #[test]
@@ -206,10 +206,9 @@ fn test_hard_puzzle2() {
         [5, 2, 9, 3, 7, 6, 4, 1, 8],
     ];
 
-    test_solve(&puzzle, &solution);
+    test_solve(&puzzle, &solution, Tier::Medium);
 }
 
-#[ignore]
 #[test]
 fn test_hard_puzzle3() {
     let puzzle: Puzzle = [
@@ -224,18 +223,21 @@ fn test_hard_puzzle3() {
         [0, 5, 8, 0, 0, 0, 0, 3, 0],
     ];
     let solution: Puzzle = [
-        [0, 6, 0, 0, 0, 3, 5, 7, 0],
-        [3, 0, 0, 7, 5, 0, 0, 0, 6],
-        [5, 0, 7, 6, 1, 8, 0, 0, 0],
-        [0, 0, 6, 9, 0, 7, 0, 5, 2],
-        [0, 0, 3, 5, 0, 0, 7, 0, 0],
-        [7, 0, 5, 0, 0, 1, 9, 0, 0],
-        [0, 7, 0, 3, 4, 6, 2, 8, 5],
+        [8, 6, 9, 4, 2, 3, 5, 7, 1],
+        [3, 1, 4, 7, 5, 9, 8, 2, 6],
+        [5, 2, 7, 6, 1, 8, 3, 4, 9],
+        [1, 8, 6, 9, 3, 7, 4, 5, 2],
+        [2, 9, 3, 5, 6, 4, 7, 1, 8],
+        [7, 4, 5, 2, 8, 1, 9, 6, 3],
+        [9, 7, 1, 3, 4, 6, 2, 8, 5],
         [6, 3, 2, 8, 7, 5, 1, 9, 4],
-        [4, 5, 8, 1, 0, 0, 6, 3, 7],
+        [4, 5, 8, 1, 9, 2, 6, 3, 7],
     ];
 
-    test_solve(&puzzle, &solution);
+    test_solve(&puzzle, &solution, Tier::Hard);
+    let mut sut = Solver::new(puzzle).unwrap();
+    sut.solve().unwrap();
+    assert!(!sut.needed_guessing());
 }
 
 #[test]
@@ -264,7 +266,7 @@ fn test_hard_puzzle4() {
         [2, 7, 6, 1, 5, 8, 3, 9, 4],
     ];
 
-    test_solve(&puzzle, &solution);
+    test_solve(&puzzle, &solution, Tier::Medium);
 }
 
 #[test]
@@ -293,7 +295,7 @@ fn test_hard_puzzle5() {
         [2, 9, 4, 8, 6, 5, 7, 3, 1],
     ];
 
-    test_solve(&puzzle, &solution);
+    test_solve(&puzzle, &solution, Tier::Hard);
 }
 
 #[test]
@@ -322,34 +324,767 @@ fn test_hard_puzzle6() {
         [9, 4, 2, 6, 3, 5, 1, 7, 8],
     ];
 
-    test_solve(&puzzle, &solution);
-}
-
-// #[test]
-// fn test_hard_puzzle7() {
-//     let puzzle: Puzzle = [
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//     ];
-
-//     let solution: Puzzle = [
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//         [0, 0, 0, 0, 0, 0, 0, 0, 0],
-//     ];
-
-//     test_solve(&puzzle, &solution);
-// }
+    test_solve(&puzzle, &solution, Tier::Medium);
+}
+
+#[test]
+fn test_hard_puzzle7() {
+    let puzzle: Puzzle = [
+        [2, 0, 0, 0, 0, 0, 9, 4, 0],
+        [0, 3, 0, 2, 0, 0, 0, 8, 0],
+        [5, 7, 8, 0, 0, 0, 6, 0, 0],
+        [3, 0, 0, 8, 5, 0, 0, 2, 0],
+        [0, 2, 0, 3, 0, 0, 0, 0, 0],
+        [0, 0, 7, 0, 0, 1, 0, 0, 0],
+        [7, 0, 0, 0, 0, 0, 0, 0, 9],
+        [0, 5, 0, 4, 0, 9, 0, 0, 0],
+        [0, 0, 0, 0, 0, 5, 4, 0, 0],
+    ];
+
+    let solution: Puzzle = [
+        [2, 1, 6, 5, 7, 8, 9, 4, 3],
+        [9, 3, 4, 2, 1, 6, 5, 8, 7],
+        [5, 7, 8, 9, 4, 3, 6, 1, 2],
+        [3, 6, 9, 8, 5, 7, 1, 2, 4],
+        [1, 2, 5, 3, 9, 4, 7, 6, 8],
+        [4, 8, 7, 6, 2, 1, 3, 9, 5],
+        [7, 4, 3, 1, 6, 2, 8, 5, 9],
+        [8, 5, 1, 4, 3, 9, 2, 7, 6],
+        [6, 9, 2, 7, 8, 5, 4, 3, 1],
+    ];
+
+    test_solve(&puzzle, &solution, Tier::Medium);
+}
+
+#[test]
+fn test_hard_puzzle8() {
+    let puzzle: Puzzle = [
+        [0, 0, 0, 0, 0, 0, 0, 0, 0],
+        [3, 0, 0, 0, 5, 0, 4, 0, 8],
+        [0, 0, 7, 0, 1, 0, 2, 3, 0],
+        [0, 0, 4, 0, 0, 9, 0, 0, 5],
+        [6, 0, 9, 4, 0, 0, 0, 8, 0],
+        [0, 0, 0, 1, 0, 2, 0, 0, 0],
+        [9, 0, 0, 0, 0, 0, 0, 0, 1],
+        [0, 5, 0, 0, 8, 0, 0, 9, 0],
+        [7, 8, 0, 0, 9, 0, 0, 5, 0],
+    ];
+
+    let solution: Puzzle = [
+        [2, 1, 8, 9, 4, 3, 5, 6, 7],
+        [3, 9, 6, 2, 5, 7, 4, 1, 8],
+        [5, 4, 7, 6, 1, 8, 2, 3, 9],
+        [1, 3, 4, 8, 6, 9, 7, 2, 5],
+        [6, 2, 9, 4, 7, 5, 1, 8, 3],
+        [8, 7, 5, 1, 3, 2, 9, 4, 6],
+        [9, 6, 3, 5, 2, 4, 8, 7, 1],
+        [4, 5, 1, 7, 8, 6, 3, 9, 2],
+        [7, 8, 2, 3, 9, 1, 6, 5, 4],
+    ];
+
+    test_solve(&puzzle, &solution, Tier::Hard);
+}
+
+#[test]
+fn test_finned_fish_puzzle() {
+    let puzzle: Puzzle = [
+        [0, 0, 6, 9, 0, 0, 0, 7, 0],
+        [0, 0, 0, 0, 0, 4, 0, 0, 0],
+        [4, 0, 5, 3, 0, 0, 9, 0, 8],
+        [0, 1, 0, 0, 0, 0, 0, 0, 0],
+        [8, 0, 0, 1, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 4, 8, 0, 0, 2],
+        [9, 0, 0, 2, 1, 0, 8, 0, 4],
+        [2, 0, 0, 0, 6, 5, 0, 0, 0],
+        [0, 4, 0, 0, 0, 9, 0, 6, 0],
+    ];
+
+    let solution: Puzzle = [
+        [3, 2, 6, 9, 8, 1, 4, 7, 5],
+        [1, 9, 8, 7, 5, 4, 6, 2, 3],
+        [4, 7, 5, 3, 2, 6, 9, 1, 8],
+        [6, 1, 4, 5, 3, 2, 7, 8, 9],
+        [8, 3, 2, 1, 9, 7, 5, 4, 6],
+        [7, 5, 9, 6, 4, 8, 1, 3, 2],
+        [9, 6, 7, 2, 1, 3, 8, 5, 4],
+        [2, 8, 1, 4, 6, 5, 3, 9, 7],
+        [5, 4, 3, 8, 7, 9, 2, 6, 1],
+    ];
+
+    test_solve(&puzzle, &solution, Tier::Hard);
+}
+
+#[test]
+fn test_wings_puzzle() {
+    let puzzle: Puzzle = [
+        [0, 0, 0, 0, 0, 9, 4, 0, 8],
+        [4, 0, 0, 0, 0, 8, 0, 9, 0],
+        [0, 0, 9, 0, 1, 0, 0, 5, 0],
+        [0, 0, 6, 3, 0, 0, 0, 0, 4],
+        [1, 0, 4, 0, 0, 0, 0, 0, 5],
+        [0, 0, 3, 7, 5, 0, 0, 0, 0],
+        [0, 2, 0, 8, 0, 0, 0, 0, 6],
+        [0, 1, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 8, 0, 3, 0, 7, 0, 0],
+    ];
+
+    let solution: Puzzle = [
+        [5, 3, 2, 6, 7, 9, 4, 1, 8],
+        [4, 6, 1, 5, 2, 8, 3, 9, 7],
+        [7, 8, 9, 4, 1, 3, 6, 5, 2],
+        [2, 5, 6, 3, 8, 1, 9, 7, 4],
+        [1, 7, 4, 9, 6, 2, 8, 3, 5],
+        [8, 9, 3, 7, 5, 4, 2, 6, 1],
+        [3, 2, 5, 8, 9, 7, 1, 4, 6],
+        [9, 1, 7, 2, 4, 6, 5, 8, 3],
+        [6, 4, 8, 1, 3, 5, 7, 2, 9],
+    ];
+
+    test_solve(&puzzle, &solution, Tier::Hard);
+}
+
+#[test]
+fn test_short_chains_puzzle() {
+    let puzzle: Puzzle = [
+        [0, 0, 5, 0, 7, 4, 6, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0, 2],
+        [7, 0, 0, 6, 2, 0, 5, 0, 3],
+        [0, 0, 0, 4, 0, 0, 1, 0, 0],
+        [8, 0, 7, 0, 0, 0, 0, 0, 0],
+        [0, 4, 0, 0, 5, 7, 0, 2, 0],
+        [0, 0, 4, 0, 0, 0, 0, 0, 8],
+        [0, 0, 1, 0, 0, 9, 0, 0, 0],
+        [2, 0, 6, 0, 0, 5, 0, 0, 0],
+    ];
+
+    let solution: Puzzle = [
+        [1, 2, 5, 3, 7, 4, 6, 8, 9],
+        [4, 6, 3, 5, 9, 8, 7, 1, 2],
+        [7, 9, 8, 6, 2, 1, 5, 4, 3],
+        [6, 5, 2, 4, 8, 3, 1, 9, 7],
+        [8, 1, 7, 9, 6, 2, 4, 3, 5],
+        [3, 4, 9, 1, 5, 7, 8, 2, 6],
+        [9, 7, 4, 2, 1, 6, 3, 5, 8],
+        [5, 8, 1, 7, 3, 9, 2, 6, 4],
+        [2, 3, 6, 8, 4, 5, 9, 7, 1],
+    ];
+
+    test_solve(&puzzle, &solution, Tier::Hard);
+}
+
+#[test]
+fn test_colouring_puzzle() {
+    let puzzle: Puzzle = [
+        [3, 0, 0, 0, 0, 0, 0, 6, 0],
+        [0, 0, 0, 0, 0, 8, 1, 0, 0],
+        [0, 0, 6, 7, 0, 0, 0, 2, 4],
+        [0, 0, 0, 0, 0, 1, 6, 8, 0],
+        [8, 0, 0, 3, 5, 0, 0, 0, 0],
+        [0, 0, 2, 0, 0, 0, 5, 0, 3],
+        [0, 0, 0, 0, 3, 0, 0, 5, 7],
+        [4, 0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 6, 5, 0, 0, 0, 0, 0, 0],
+    ];
+
+    let solution: Puzzle = [
+        [3, 4, 1, 5, 9, 2, 7, 6, 8],
+        [2, 7, 9, 4, 6, 8, 1, 3, 5],
+        [5, 8, 6, 7, 1, 3, 9, 2, 4],
+        [7, 5, 3, 2, 4, 1, 6, 8, 9],
+        [8, 9, 4, 3, 5, 6, 2, 7, 1],
+        [6, 1, 2, 9, 8, 7, 5, 4, 3],
+        [1, 2, 8, 6, 3, 9, 4, 5, 7],
+        [4, 3, 7, 1, 2, 5, 8, 9, 6],
+        [9, 6, 5, 8, 7, 4, 3, 1, 2],
+    ];
+
+    test_solve(&puzzle, &solution, Tier::Expert);
+    let mut sut = Solver::new(puzzle).unwrap();
+    sut.set_tracing(true);
+    sut.solve().unwrap();
+    assert!(!sut.needed_guessing());
+    assert!(sut.get_trace().any(|deduction| deduction.technique == "Colour Wrap"));
+}
+
+#[test]
+fn test_uniqueness_puzzle() {
+    let puzzle: Puzzle = [
+        [5, 0, 4, 0, 1, 0, 0, 0, 2],
+        [0, 0, 8, 0, 6, 2, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0, 0],
+        [3, 0, 6, 4, 0, 0, 0, 0, 0],
+        [0, 1, 0, 0, 5, 0, 8, 0, 9],
+        [0, 0, 0, 1, 7, 0, 0, 5, 0],
+        [0, 0, 0, 0, 3, 8, 0, 0, 0],
+        [0, 7, 0, 0, 0, 0, 4, 0, 0],
+        [8, 0, 1, 0, 0, 0, 6, 0, 0],
+    ];
+
+    let solution: Puzzle = [
+        [5, 6, 4, 3, 1, 7, 9, 8, 2],
+        [1, 9, 8, 5, 6, 2, 7, 4, 3],
+        [7, 2, 3, 8, 9, 4, 5, 1, 6],
+        [3, 5, 6, 4, 8, 9, 2, 7, 1],
+        [4, 1, 7, 2, 5, 3, 8, 6, 9],
+        [2, 8, 9, 1, 7, 6, 3, 5, 4],
+        [6, 4, 2, 7, 3, 8, 1, 9, 5],
+        [9, 7, 5, 6, 2, 1, 4, 3, 8],
+        [8, 3, 1, 9, 4, 5, 6, 2, 7],
+    ];
+
+    test_solve(&puzzle, &solution, Tier::Expert);
+}
+
+#[test]
+fn test_uniqueness_switched_off() {
+    let puzzle: Puzzle = [
+        [5, 0, 4, 0, 1, 0, 0, 0, 2],
+        [0, 0, 8, 0, 6, 2, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0, 0],
+        [3, 0, 6, 4, 0, 0, 0, 0, 0],
+        [0, 1, 0, 0, 5, 0, 8, 0, 9],
+        [0, 0, 0, 1, 7, 0, 0, 5, 0],
+        [0, 0, 0, 0, 3, 8, 0, 0, 0],
+        [0, 7, 0, 0, 0, 0, 4, 0, 0],
+        [8, 0, 1, 0, 0, 0, 6, 0, 0],
+    ];
+
+    let mut sut = Solver::new(puzzle).unwrap();
+    sut.set_uniqueness(false);
+    sut.set_tracing(true);
+    sut.solve().unwrap();
+    assert!(sut.get_trace().all(|deduction| !deduction.technique.starts_with("Unique")));
+}
+
+#[test]
+fn test_uniqueness_after_als_puzzle() {
+    let puzzle: Puzzle = [
+        [2, 7, 0, 0, 0, 0, 0, 0, 0],
+        [0, 5, 0, 4, 0, 2, 0, 9, 8],
+        [0, 0, 0, 0, 1, 0, 0, 3, 0],
+        [0, 0, 2, 0, 0, 3, 0, 8, 4],
+        [0, 0, 0, 2, 0, 0, 0, 0, 0],
+        [0, 4, 0, 0, 0, 8, 0, 7, 0],
+        [0, 0, 8, 0, 6, 0, 3, 0, 0],
+        [0, 0, 7, 9, 0, 0, 0, 4, 0],
+        [0, 0, 0, 0, 0, 7, 0, 1, 0],
+    ];
+
+    let solution: Puzzle = [
+        [2, 7, 3, 8, 9, 6, 4, 5, 1],
+        [1, 5, 6, 4, 3, 2, 7, 9, 8],
+        [8, 9, 4, 7, 1, 5, 2, 3, 6],
+        [5, 6, 2, 1, 7, 3, 9, 8, 4],
+        [7, 8, 1, 2, 4, 9, 5, 6, 3],
+        [3, 4, 9, 6, 5, 8, 1, 7, 2],
+        [9, 1, 8, 5, 6, 4, 3, 2, 7],
+        [6, 3, 7, 9, 2, 1, 8, 4, 5],
+        [4, 2, 5, 3, 8, 7, 6, 1, 9],
+    ];
+
+    test_solve(&puzzle, &solution, Tier::Expert);
+}
+
+#[test]
+fn test_uniqueness_after_als_switched_off() {
+    let puzzle: Puzzle = [
+        [2, 7, 0, 0, 0, 0, 0, 0, 0],
+        [0, 5, 0, 4, 0, 2, 0, 9, 8],
+        [0, 0, 0, 0, 1, 0, 0, 3, 0],
+        [0, 0, 2, 0, 0, 3, 0, 8, 4],
+        [0, 0, 0, 2, 0, 0, 0, 0, 0],
+        [0, 4, 0, 0, 0, 8, 0, 7, 0],
+        [0, 0, 8, 0, 6, 0, 3, 0, 0],
+        [0, 0, 7, 9, 0, 0, 0, 4, 0],
+        [0, 0, 0, 0, 0, 7, 0, 1, 0],
+    ];
+
+    let solution: Puzzle = [
+        [2, 7, 3, 8, 9, 6, 4, 5, 1],
+        [1, 5, 6, 4, 3, 2, 7, 9, 8],
+        [8, 9, 4, 7, 1, 5, 2, 3, 6],
+        [5, 6, 2, 1, 7, 3, 9, 8, 4],
+        [7, 8, 1, 2, 4, 9, 5, 6, 3],
+        [3, 4, 9, 6, 5, 8, 1, 7, 2],
+        [9, 1, 8, 5, 6, 4, 3, 2, 7],
+        [6, 3, 7, 9, 2, 1, 8, 4, 5],
+        [4, 2, 5, 3, 8, 7, 6, 1, 9],
+    ];
+
+    let mut sut = Solver::new(puzzle).unwrap();
+    sut.set_uniqueness(false);
+    sut.solve().unwrap();
+    assert!(sut.needed_guessing());
+    check_solution(&sut.get_solution(), &solution);
+}
+
+#[test]
+fn test_als_puzzle() {
+    let puzzle: Puzzle = [
+        [0, 5, 0, 2, 6, 0, 0, 0, 3],
+        [6, 9, 0, 0, 3, 0, 0, 5, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0, 0],
+        [8, 0, 0, 0, 0, 0, 0, 4, 1],
+        [0, 0, 5, 0, 0, 0, 6, 0, 0],
+        [1, 3, 0, 0, 7, 0, 8, 0, 0],
+        [0, 0, 0, 3, 0, 0, 0, 0, 4],
+        [0, 0, 0, 0, 5, 8, 0, 0, 0],
+        [0, 2, 9, 0, 0, 7, 3, 0, 0],
+    ];
+
+    let solution: Puzzle = [
+        [4, 5, 7, 2, 6, 1, 9, 8, 3],
+        [6, 9, 8, 7, 3, 4, 1, 5, 2],
+        [2, 1, 3, 9, 8, 5, 4, 7, 6],
+        [8, 7, 6, 5, 9, 3, 2, 4, 1],
+        [9, 4, 5, 8, 1, 2, 6, 3, 7],
+        [1, 3, 2, 4, 7, 6, 8, 9, 5],
+        [7, 8, 1, 3, 2, 9, 5, 6, 4],
+        [3, 6, 4, 1, 5, 8, 7, 2, 9],
+        [5, 2, 9, 6, 4, 7, 3, 1, 8],
+    ];
+
+    test_solve(&puzzle, &solution, Tier::Expert);
+}
+
+#[test]
+fn test_guessing_puzzle() {
+    let puzzle: Puzzle = [
+        [2, 0, 0, 0, 0, 0, 3, 6, 0],
+        [0, 7, 0, 0, 0, 3, 1, 0, 0],
+        [8, 0, 0, 9, 0, 0, 0, 0, 5],
+        [0, 0, 4, 0, 6, 0, 0, 2, 9],
+        [0, 1, 0, 4, 0, 0, 0, 0, 0],
+        [6, 0, 0, 0, 3, 0, 0, 0, 0],
+        [0, 0, 0, 1, 0, 0, 0, 5, 0],
+        [0, 0, 0, 0, 9, 4, 0, 0, 6],
+        [0, 6, 0, 0, 0, 8, 0, 0, 0],
+    ];
+
+    let solution: Puzzle = [
+        [2, 9, 1, 8, 4, 5, 3, 6, 7],
+        [4, 7, 5, 6, 2, 3, 1, 9, 8],
+        [8, 3, 6, 9, 1, 7, 2, 4, 5],
+        [3, 8, 4, 7, 6, 1, 5, 2, 9],
+        [5, 1, 9, 4, 8, 2, 6, 7, 3],
+        [6, 2, 7, 5, 3, 9, 4, 8, 1],
+        [9, 4, 3, 1, 7, 6, 8, 5, 2],
+        [1, 5, 8, 2, 9, 4, 7, 3, 6],
+        [7, 6, 2, 3, 5, 8, 9, 1, 4],
+    ];
+
+    assert_eq!(rate(&Layout::default(), &puzzle).unwrap().tier, Tier::Extreme);
+    let mut sut = Solver::new(puzzle).unwrap();
+    sut.solve().unwrap();
+    assert!(sut.needed_guessing());
+    check_solution(&sut.get_solution(), &solution);
+}
+
+#[test]
+fn test_invalid_puzzle() {
+    let mut puzzle = PUZZLE1;
+    puzzle[0][2] = 5;
+    let expected = SolverError::DuplicateValue { value: 5, cells: [(0, 0), (0, 2)] };
+    assert_eq!(Solver::new(puzzle).err(), Some(expected));
+}
+
+#[test]
+fn test_contradiction() {
+    let mut puzzle = PUZZLE1;
+    puzzle[0][3] = 2;
+    let mut sut = Solver::new(puzzle).unwrap();
+    assert!(sut.solve().is_err());
+}
+
+#[test]
+fn test_trace() {
+    let mut sut = Solver::new(PUZZLE1).unwrap();
+    sut.solve().unwrap();
+    assert_eq!(sut.get_trace().count(), 0);
+
+    let mut sut = Solver::new(PUZZLE1).unwrap();
+    sut.set_tracing(true);
+    sut.solve().unwrap();
+    let mut replayed = PUZZLE1;
+    for step in sut.get_trace() {
+        for ((row, col), value) in step.placements.iter() {
+            assert_eq!(replayed[*row][*col], 0);
+            replayed[*row][*col] = *value;
+        }
+    }
+    assert_eq!(replayed, SOLUTION1);
+    let first = sut.get_trace().next().unwrap();
+    assert_eq!(first.to_string(), "Naked Single places 5 at r5c5.");
+}
+
+#[test]
+fn test_beginner_pipeline() {
+    use crate::solve::technique::*;
+    let mut sut = Solver::new(PUZZLE1).unwrap();
+    sut.set_pipeline(Pipeline::beginner());
+    sut.solve().unwrap();
+    assert!(!sut.needed_guessing());
+    check_solution(&sut.get_solution(), &SOLUTION1);
+
+    let mut sut = Solver::new(PUZZLE1).unwrap();
+    sut.set_pipeline(Pipeline::new(vec![Box::new(HiddenSets(SetSize::Pair..=SetSize::Quad))]));
+    sut.solve().unwrap();
+    assert!(sut.needed_guessing());
+    check_solution(&sut.get_solution(), &SOLUTION1);
+}
+
+fn test_solve_layout<const N: usize>(layout: Layout<N>, puzzle: &Puzzle<N>, expected: &Puzzle<N>) {
+    let oracle = crate::solve::dancing_links::solve(&layout, puzzle).unwrap();
+    assert_eq!(&oracle, expected);
+    let mut sut = Solver::with_layout(layout, *puzzle).unwrap();
+    sut.solve().unwrap();
+    assert!(!sut.needed_guessing());
+    assert_eq!(&sut.get_solution(), expected);
+}
+
+#[test]
+fn test_small_grids() {
+    let puzzle = [
+        [0, 4, 0, 0],
+        [0, 0, 2, 0],
+        [0, 0, 0, 1],
+        [3, 0, 0, 0],
+    ];
+    let solution = [
+        [2, 4, 1, 3],
+        [1, 3, 2, 4],
+        [4, 2, 3, 1],
+        [3, 1, 4, 2],
+    ];
+    test_solve_layout(Layout::default(), &puzzle, &solution);
+    let puzzle = [
+        [0, 0, 6, 1, 3, 0],
+        [0, 3, 5, 0, 2, 0],
+        [0, 0, 2, 0, 0, 0],
+        [6, 0, 0, 0, 0, 2],
+        [0, 0, 4, 0, 5, 0],
+        [0, 0, 0, 0, 1, 0],
+    ];
+    let solution = [
+        [4, 2, 6, 1, 3, 5],
+        [1, 3, 5, 4, 2, 6],
+        [3, 4, 2, 5, 6, 1],
+        [6, 5, 1, 3, 4, 2],
+        [2, 1, 4, 6, 5, 3],
+        [5, 6, 3, 2, 1, 4],
+    ];
+    test_solve_layout(Layout::default(), &puzzle, &solution);
+}
+
+#[test]
+fn test_rectangular_boxes() {
+    let puzzle = [
+        [0, 7, 9, 10, 0, 0, 0, 5, 0, 0, 0, 12],
+        [0, 11, 6, 12, 3, 0, 9, 0, 0, 0, 0, 5],
+        [1, 2, 0, 0, 8, 11, 0, 0, 0, 7, 0, 10],
+        [0, 3, 0, 7, 4, 0, 11, 0, 0, 5, 12, 0],
+        [6, 0, 12, 8, 0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 1, 0, 2, 0, 5, 12, 0, 9, 0, 10, 7],
+        [7, 10, 0, 0, 0, 6, 0, 0, 0, 0, 0, 11],
+        [0, 0, 0, 11, 0, 0, 3, 9, 0, 6, 1, 4],
+        [2, 0, 0, 0, 0, 12, 0, 11, 7, 10, 3, 0],
+        [0, 0, 7, 0, 0, 4, 2, 0, 0, 0, 5, 6],
+        [0, 8, 0, 0, 10, 9, 7, 3, 0, 0, 2, 1],
+        [0, 0, 0, 0, 12, 0, 0, 0, 10, 9, 0, 0],
+    ];
+    let solution = [
+        [3, 7, 9, 10, 1, 2, 4, 5, 8, 11, 6, 12],
+        [8, 11, 6, 12, 3, 7, 9, 10, 1, 2, 4, 5],
+        [1, 2, 4, 5, 8, 11, 6, 12, 3, 7, 9, 10],
+        [9, 3, 10, 7, 4, 1, 11, 2, 6, 5, 12, 8],
+        [6, 5, 12, 8, 9, 3, 10, 7, 4, 1, 11, 2],
+        [4, 1, 11, 2, 6, 5, 12, 8, 9, 3, 10, 7],
+        [7, 10, 3, 9, 2, 6, 1, 4, 5, 12, 8, 11],
+        [5, 12, 8, 11, 7, 10, 3, 9, 2, 6, 1, 4],
+        [2, 6, 1, 4, 5, 12, 8, 11, 7, 10, 3, 9],
+        [10, 9, 7, 3, 11, 4, 2, 1, 12, 8, 5, 6],
+        [12, 8, 5, 6, 10, 9, 7, 3, 11, 4, 2, 1],
+        [11, 4, 2, 1, 12, 8, 5, 6, 10, 9, 7, 3],
+    ];
+    test_solve_layout(Layout::default(), &puzzle, &solution);
+}
+
+#[test]
+fn test_large_grid() {
+    const SIZE: usize = 25;
+    let solution = crate::solve::dancing_links::solve(&Layout::default(), &[[0; SIZE]; SIZE])
+        .unwrap();
+    let mut puzzle = solution;
+    for (row, cells) in puzzle.iter_mut().enumerate() {
+        cells[row] = 0;
+        cells[(row * 7 + 3) % SIZE] = 0;
+    }
+    test_solve_layout(Layout::default(), &puzzle, &solution);
+}
+
+#[test]
+fn test_large_grid_guessing() {
+    let puzzle: Puzzle<16> = [
+        [14, 0, 13, 0, 0, 0, 0, 2, 0, 0, 0, 16, 6, 0, 4, 0],
+        [0, 0, 2, 0, 7, 11, 12, 0, 0, 0, 5, 0, 15, 0, 0, 0],
+        [0, 0, 6, 0, 9, 0, 0, 16, 0, 15, 2, 0, 0, 0, 0, 0],
+        [15, 9, 0, 0, 0, 0, 8, 0, 7, 0, 0, 0, 0, 0, 5, 10],
+        [0, 10, 0, 1, 0, 0, 0, 7, 16, 0, 0, 0, 0, 0, 9, 0],
+        [0, 0, 0, 0, 11, 3, 0, 4, 0, 9, 14, 0, 10, 0, 13, 0],
+        [0, 0, 9, 7, 0, 0, 10, 8, 0, 0, 4, 0, 3, 16, 15, 0],
+        [0, 0, 0, 0, 0, 9, 0, 0, 3, 0, 0, 12, 11, 2, 0, 0],
+        [0, 7, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 12, 0, 16, 0, 13, 0, 1, 14, 0, 0, 0, 0, 3, 6],
+        [5, 14, 0, 4, 0, 0, 0, 10, 0, 2, 7, 0, 16, 0, 0, 0],
+        [0, 15, 0, 0, 0, 0, 0, 11, 10, 0, 0, 8, 7, 13, 0, 14],
+        [0, 13, 0, 12, 10, 0, 0, 0, 5, 0, 0, 0, 0, 7, 0, 0],
+        [0, 0, 0, 0, 0, 0, 7, 0, 0, 16, 0, 0, 0, 0, 0, 0],
+        [0, 16, 0, 11, 15, 0, 0, 0, 0, 0, 9, 0, 0, 12, 0, 1],
+        [6, 0, 0, 15, 13, 0, 1, 12, 8, 7, 0, 0, 0, 5, 10, 0],
+    ];
+
+    let solution: Puzzle<16> = [
+        [14, 12, 13, 10, 3, 15, 5, 2, 11, 1, 8, 16, 6, 9, 4, 7],
+        [4, 1, 2, 3, 7, 11, 12, 13, 9, 6, 5, 10, 15, 8, 14, 16],
+        [7, 5, 6, 8, 9, 10, 14, 16, 4, 15, 2, 3, 1, 11, 12, 13],
+        [15, 9, 11, 16, 4, 1, 8, 6, 7, 12, 13, 14, 2, 3, 5, 10],
+        [3, 10, 4, 1, 12, 13, 2, 7, 16, 11, 15, 5, 14, 6, 9, 8],
+        [16, 2, 8, 5, 11, 3, 15, 4, 6, 9, 14, 7, 10, 1, 13, 12],
+        [12, 11, 9, 7, 14, 6, 10, 8, 2, 13, 4, 1, 3, 16, 15, 5],
+        [13, 6, 15, 14, 1, 9, 16, 5, 3, 8, 10, 12, 11, 2, 7, 4],
+        [11, 7, 10, 13, 2, 14, 3, 1, 15, 5, 16, 6, 12, 4, 8, 9],
+        [2, 8, 12, 9, 16, 7, 13, 15, 1, 14, 11, 4, 5, 10, 3, 6],
+        [5, 14, 3, 4, 8, 12, 6, 10, 13, 2, 7, 9, 16, 15, 1, 11],
+        [1, 15, 16, 6, 5, 4, 9, 11, 10, 3, 12, 8, 7, 13, 2, 14],
+        [9, 13, 1, 12, 10, 2, 11, 14, 5, 4, 6, 15, 8, 7, 16, 3],
+        [10, 3, 5, 2, 6, 8, 7, 9, 12, 16, 1, 13, 4, 14, 11, 15],
+        [8, 16, 7, 11, 15, 5, 4, 3, 14, 10, 9, 2, 13, 12, 6, 1],
+        [6, 4, 14, 15, 13, 16, 1, 12, 8, 7, 3, 11, 9, 5, 10, 2],
+    ];
+
+    let start = std::time::Instant::now();
+    let mut sut = Solver::with_layout(Layout::default(), puzzle).unwrap();
+    sut.solve().unwrap();
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+    assert!(sut.needed_guessing());
+    assert_eq!(sut.get_solution(), solution);
+}
+
+#[test]
+fn test_jigsaw() {
+    let regions = [
+        [0, 0, 0, 1, 1, 2, 2, 2, 2],
+        [0, 0, 0, 1, 1, 1, 2, 2, 2],
+        [0, 0, 3, 1, 1, 1, 1, 2, 2],
+        [0, 3, 3, 4, 4, 4, 4, 5, 5],
+        [3, 3, 3, 4, 4, 5, 5, 5, 5],
+        [3, 3, 3, 4, 4, 7, 5, 5, 5],
+        [6, 6, 6, 7, 4, 7, 8, 8, 8],
+        [6, 6, 6, 7, 7, 7, 8, 8, 8],
+        [6, 6, 6, 7, 7, 7, 8, 8, 8],
+    ];
+    let puzzle = [
+        [0, 0, 0, 0, 1, 0, 3, 0, 0],
+        [0, 1, 0, 0, 0, 0, 7, 5, 2],
+        [9, 0, 0, 0, 0, 0, 6, 4, 0],
+        [0, 0, 5, 0, 0, 3, 0, 0, 0],
+        [0, 0, 0, 5, 0, 0, 0, 9, 0],
+        [1, 0, 0, 2, 8, 0, 0, 0, 0],
+        [0, 0, 7, 0, 0, 0, 9, 6, 0],
+        [0, 0, 0, 0, 0, 0, 8, 0, 0],
+        [6, 0, 4, 0, 0, 5, 0, 0, 0],
+    ];
+    let solution = [
+        [7, 5, 2, 4, 1, 6, 3, 8, 9],
+        [4, 1, 6, 8, 3, 9, 7, 5, 2],
+        [9, 3, 8, 7, 5, 2, 6, 4, 1],
+        [8, 4, 5, 9, 7, 3, 1, 2, 6],
+        [2, 7, 3, 5, 6, 1, 4, 9, 8],
+        [1, 6, 9, 2, 8, 4, 5, 7, 3],
+        [3, 2, 7, 1, 4, 8, 9, 6, 5],
+        [5, 9, 1, 6, 2, 7, 8, 3, 4],
+        [6, 8, 4, 3, 9, 5, 2, 1, 7],
+    ];
+    test_solve_layout(Layout::from_regions(regions).unwrap(), &puzzle, &solution);
+}
+
+#[test]
+fn test_diagonals() {
+    let puzzle = [
+        [0, 0, 9, 7, 0, 0, 8, 0, 0],
+        [8, 0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 5, 1, 0, 0, 2, 3, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 7, 0],
+        [0, 3, 0, 0, 0, 0, 0, 1, 0],
+        [5, 7, 0, 0, 2, 0, 4, 0, 0],
+        [0, 1, 0, 0, 8, 6, 0, 0, 4],
+        [0, 8, 3, 0, 0, 5, 0, 2, 6],
+        [0, 0, 0, 4, 1, 0, 0, 8, 0],
+    ];
+    let solution = [
+        [3, 6, 9, 7, 5, 1, 8, 4, 2],
+        [8, 4, 2, 3, 6, 9, 7, 5, 1],
+        [7, 5, 1, 8, 4, 2, 3, 6, 9],
+        [1, 9, 8, 6, 3, 4, 2, 7, 5],
+        [2, 3, 4, 5, 9, 7, 6, 1, 8],
+        [5, 7, 6, 1, 2, 8, 4, 9, 3],
+        [9, 1, 7, 2, 8, 6, 5, 3, 4],
+        [4, 8, 3, 9, 7, 5, 1, 2, 6],
+        [6, 2, 5, 4, 1, 3, 9, 8, 7],
+    ];
+    test_solve_layout(Layout::default().with_diagonals(), &puzzle, &solution);
+    let layout = Layout::default();
+    let count = crate::solve::solution_count::count_solutions(&layout, &puzzle, 2).unwrap();
+    assert!(!count.is_unique());
+}
+
+#[test]
+fn test_windows() {
+    let puzzle = [
+        [7, 0, 2, 0, 0, 3, 0, 0, 0],
+        [9, 0, 0, 0, 0, 2, 0, 1, 4],
+        [0, 4, 6, 8, 0, 0, 0, 3, 0],
+        [0, 0, 1, 0, 0, 0, 0, 0, 0],
+        [4, 6, 0, 0, 0, 1, 0, 0, 0],
+        [5, 0, 0, 0, 4, 0, 1, 0, 0],
+        [0, 0, 0, 0, 3, 7, 0, 0, 1],
+        [6, 0, 0, 0, 0, 8, 0, 2, 0],
+        [0, 5, 0, 0, 0, 0, 6, 8, 0],
+    ];
+    let solution = [
+        [7, 8, 2, 4, 1, 3, 9, 5, 6],
+        [9, 3, 5, 7, 6, 2, 8, 1, 4],
+        [1, 4, 6, 8, 5, 9, 7, 3, 2],
+        [8, 2, 1, 9, 7, 5, 4, 6, 3],
+        [4, 6, 9, 3, 8, 1, 2, 7, 5],
+        [5, 7, 3, 2, 4, 6, 1, 9, 8],
+        [2, 9, 8, 6, 3, 7, 5, 4, 1],
+        [6, 1, 4, 5, 9, 8, 3, 2, 7],
+        [3, 5, 7, 1, 2, 4, 6, 8, 9],
+    ];
+    test_solve_layout(Layout::default().with_windows(), &puzzle, &solution);
+}
+
+#[test]
+fn test_killer() {
+    let cage = |cells: &[Point], sum| Cage { cells: cells.to_vec(), sum };
+    let cages = vec![
+        cage(&[(0, 0), (1, 0), (1, 1)], 17),
+        cage(&[(0, 1)], 4),
+        cage(&[(0, 2)], 3),
+        cage(&[(0, 3), (0, 4), (1, 4)], 15),
+        cage(&[(0, 5), (0, 6), (0, 7)], 14),
+        cage(&[(0, 8)], 8),
+        cage(&[(1, 2), (1, 3)], 11),
+        cage(&[(1, 5), (2, 5), (3, 5)], 12),
+        cage(&[(1, 6), (1, 7), (2, 6), (3, 6)], 22),
+        cage(&[(1, 8)], 3),
+        cage(&[(2, 0), (3, 0)], 8),
+        cage(&[(2, 1), (3, 1), (4, 1)], 20),
+        cage(&[(2, 2), (2, 3)], 9),
+        cage(&[(2, 4), (3, 2), (3, 3), (3, 4)], 25),
+        cage(&[(2, 7), (2, 8), (3, 7)], 14),
+        cage(&[(3, 8), (4, 8)], 5),
+        cage(&[(4, 0), (5, 0)], 6),
+        cage(&[(4, 2), (4, 3), (5, 2)], 13),
+        cage(&[(4, 4)], 6),
+        cage(&[(4, 5), (4, 6), (4, 7)], 15),
+        cage(&[(5, 1)], 2),
+        cage(&[(5, 3), (6, 3), (6, 4), (7, 4)], 22),
+        cage(&[(5, 4)], 7),
+        cage(&[(5, 5), (5, 6), (6, 6), (6, 7)], 22),
+        cage(&[(5, 7), (5, 8), (6, 8)], 18),
+        cage(&[(6, 0)], 3),
+        cage(&[(6, 1)], 6),
+        cage(&[(6, 2), (7, 2)], 3),
+        cage(&[(6, 5), (7, 5), (8, 4), (8, 5)], 18),
+        cage(&[(7, 0), (8, 0)], 12),
+        cage(&[(7, 1), (8, 1)], 12),
+        cage(&[(7, 3), (8, 3)], 8),
+        cage(&[(7, 6)], 6),
+        cage(&[(7, 7), (8, 6), (8, 7)], 16),
+        cage(&[(7, 8), (8, 8)], 11),
+        cage(&[(8, 2)], 9),
+    ];
+    let solution = [
+        [9, 4, 3, 6, 1, 7, 5, 2, 8],
+        [7, 1, 6, 5, 8, 2, 4, 9, 3],
+        [2, 8, 5, 4, 3, 9, 7, 1, 6],
+        [6, 3, 8, 9, 5, 1, 2, 7, 4],
+        [5, 9, 7, 2, 6, 4, 8, 3, 1],
+        [1, 2, 4, 3, 7, 8, 9, 6, 5],
+        [3, 6, 2, 8, 9, 5, 1, 4, 7],
+        [4, 5, 1, 7, 2, 3, 6, 8, 9],
+        [8, 7, 9, 1, 4, 6, 3, 5, 2],
+    ];
+    test_solve_layout(Layout::default().with_cages(cages).unwrap(), &[[0; 9]; 9], &solution);
+}
+
+#[test]
+fn test_chess_constraints() {
+    let puzzle = [
+        [0, 0, 0, 0, 0, 6, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0, 0],
+        [1, 0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 1, 0, 0, 0],
+        [0, 0, 0, 0, 9, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 4, 8, 0, 0, 0],
+        [0, 4, 0, 0, 0, 0, 0, 0, 0],
+    ];
+    let solution = [
+        [4, 8, 3, 7, 2, 6, 1, 5, 9],
+        [7, 2, 6, 1, 5, 9, 4, 8, 3],
+        [1, 5, 9, 4, 8, 3, 7, 2, 6],
+        [8, 3, 7, 2, 6, 1, 5, 9, 4],
+        [2, 6, 1, 5, 9, 4, 8, 3, 7],
+        [5, 9, 4, 8, 3, 7, 2, 6, 1],
+        [3, 7, 2, 6, 1, 5, 9, 4, 8],
+        [6, 1, 5, 9, 4, 8, 3, 7, 2],
+        [9, 4, 8, 3, 7, 2, 6, 1, 5],
+    ];
+    let layout = Layout::default().with_anti_knight().with_anti_king().with_non_consecutive();
+    let count = crate::solve::solution_count::count_solutions(&layout, &puzzle, 2).unwrap();
+    assert!(count.is_unique());
+    test_solve_layout(layout, &puzzle, &solution);
+    let layout = Layout::default();
+    let count = crate::solve::solution_count::count_solutions(&layout, &puzzle, 2).unwrap();
+    assert!(!count.is_unique());
+}
+
+#[test]
+fn test_lines() {
+    let lines = vec![
+        Line::new(LineKind::Thermometer, vec![(4, 1), (4, 0), (5, 0)]),
+        Line::new(LineKind::Whispers, vec![(5, 5), (6, 6), (7, 6), (8, 5), (7, 4)]),
+        Line::new(LineKind::Arrow, vec![(2, 4), (1, 5), (1, 6), (0, 6)]),
+        Line::new(LineKind::Palindrome, vec![(3, 4), (3, 5), (4, 6)]),
+    ];
+    let puzzle = [
+        [0, 0, 0, 0, 6, 0, 0, 0, 7],
+        [0, 4, 0, 5, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 6, 0, 0],
+        [0, 3, 5, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 7, 0, 1, 0, 2],
+        [0, 0, 0, 0, 0, 0, 0, 3, 8],
+        [0, 2, 0, 0, 0, 0, 0, 0, 0],
+        [0, 9, 0, 8, 0, 0, 3, 0, 5],
+        [0, 0, 0, 0, 0, 0, 0, 6, 0],
+    ];
+    let solution = [
+        [3, 8, 9, 2, 6, 4, 5, 1, 7],
+        [7, 4, 6, 5, 9, 1, 2, 8, 3],
+        [5, 1, 2, 3, 8, 7, 6, 4, 9],
+        [2, 3, 5, 4, 1, 8, 7, 9, 6],
+        [8, 6, 4, 9, 7, 3, 1, 5, 2],
+        [9, 7, 1, 6, 5, 2, 4, 3, 8],
+        [6, 2, 8, 1, 3, 5, 9, 7, 4],
+        [1, 9, 7, 8, 4, 6, 3, 2, 5],
+        [4, 5, 3, 7, 2, 9, 8, 6, 1],
+    ];
+    let layout = Layout::default().with_lines(lines).unwrap();
+    let count = crate::solve::solution_count::count_solutions(&layout, &puzzle, 2).unwrap();
+    assert!(count.is_unique());
+    test_solve_layout(layout, &puzzle, &solution);
+}
@@ -0,0 +1,142 @@
+use super::*;
+use crate::common::grid_size::GRID_SIZE;
+
+const SOLUTION: [[usize; GRID_SIZE]; GRID_SIZE] = [
+    [5, 3, 4, 6, 7, 8, 9, 1, 2],
+    [6, 7, 2, 1, 9, 5, 3, 4, 8],
+    [1, 9, 8, 3, 4, 2, 5, 6, 7],
+    [8, 5, 9, 7, 6, 1, 4, 2, 3],
+    [4, 2, 6, 8, 5, 3, 7, 9, 1],
+    [7, 1, 3, 9, 2, 4, 8, 5, 6],
+    [9, 6, 1, 5, 3, 7, 2, 8, 4],
+    [2, 8, 7, 4, 1, 9, 6, 3, 5],
+    [3, 4, 5, 2, 8, 6, 1, 7, 9],
+];
+
+fn notes_with_floor() -> Notes {
+    let mut notes: Notes = [[CandidateSet::EMPTY; GRID_SIZE]; GRID_SIZE];
+    notes[0][0] = CandidateSet::from_bits(0b000_000_011);
+    notes[0][3] = CandidateSet::from_bits(0b000_000_011);
+    notes
+}
+
+fn bug_notes() -> Notes {
+    let mut notes: Notes = [[CandidateSet::EMPTY; GRID_SIZE]; GRID_SIZE];
+    for (row, solution_row) in SOLUTION.iter().enumerate() {
+        for (col, value) in solution_row.iter().enumerate() {
+            notes[row][col] = [*value, value % GRID_SIZE + 1].into_iter().collect();
+        }
+    }
+    notes
+}
+
+#[test]
+fn test_type_1() {
+    let mut notes = notes_with_floor();
+    notes[1][0] = CandidateSet::from_bits(0b000_000_011);
+    notes[1][3] = CandidateSet::from_bits(0b000_010_011);
+    let rectangle = Uniqueness {
+        kind: UniquenessKind::UniqueRectangle1,
+        cells: vec![(0, 0), (0, 3), (1, 0), (1, 3)],
+        eliminations: vec![((1, 3), 1), ((1, 3), 2)],
+    };
+    assert_eq!(find_unique_rectangles(&Layout::default(), &notes), vec![rectangle]);
+    assert!(!use_uniqueness(&Layout::default(), &mut notes).is_empty());
+    assert_eq!(notes[1][3].bits(), 0b000_010_000);
+}
+
+#[test]
+fn test_type_2() {
+    let mut notes = notes_with_floor();
+    notes[1][0] = CandidateSet::from_bits(0b000_010_011);
+    notes[1][3] = CandidateSet::from_bits(0b000_010_011);
+    notes[1][6] = CandidateSet::from_bits(0b001_010_000);
+    notes[4][0] = CandidateSet::from_bits(0b010_010_000);
+    let rectangle = Uniqueness {
+        kind: UniquenessKind::UniqueRectangle2,
+        cells: vec![(0, 0), (0, 3), (1, 0), (1, 3)],
+        eliminations: vec![((1, 6), 5)],
+    };
+    assert_eq!(find_unique_rectangles(&Layout::default(), &notes), vec![rectangle]);
+    assert!(!use_uniqueness(&Layout::default(), &mut notes).is_empty());
+    assert_eq!(notes[1][6].bits(), 0b001_000_000);
+    assert_eq!(notes[4][0].bits(), 0b010_010_000);
+}
+
+#[test]
+fn test_type_3() {
+    let mut notes = notes_with_floor();
+    notes[1][0] = CandidateSet::from_bits(0b000_010_011);
+    notes[1][3] = CandidateSet::from_bits(0b000_100_011);
+    notes[1][7] = CandidateSet::from_bits(0b000_110_000);
+    notes[1][8] = CandidateSet::from_bits(0b100_110_000);
+    notes[1][5] = CandidateSet::from_bits(0b100_001_000);
+    let rectangle = Uniqueness {
+        kind: UniquenessKind::UniqueRectangle3,
+        cells: vec![(0, 0), (0, 3), (1, 0), (1, 3)],
+        eliminations: vec![((1, 8), 5), ((1, 8), 6)],
+    };
+    assert_eq!(find_unique_rectangles(&Layout::default(), &notes), vec![rectangle]);
+    assert!(!use_uniqueness(&Layout::default(), &mut notes).is_empty());
+    assert_eq!(notes[1][8].bits(), 0b100_000_000);
+    assert_eq!(notes[1][5].bits(), 0b100_001_000);
+}
+
+#[test]
+fn test_type_4() {
+    let mut notes = notes_with_floor();
+    notes[1][0] = CandidateSet::from_bits(0b000_010_011);
+    notes[1][3] = CandidateSet::from_bits(0b001_100_011);
+    notes[1][5] = CandidateSet::from_bits(0b010_000_010);
+    let rectangle = Uniqueness {
+        kind: UniquenessKind::UniqueRectangle4,
+        cells: vec![(0, 0), (0, 3), (1, 0), (1, 3)],
+        eliminations: vec![((1, 0), 2), ((1, 3), 2)],
+    };
+    assert_eq!(find_unique_rectangles(&Layout::default(), &notes), vec![rectangle]);
+    assert!(!use_uniqueness(&Layout::default(), &mut notes).is_empty());
+    assert_eq!(notes[1][0].bits(), 0b000_010_001);
+    assert_eq!(notes[1][3].bits(), 0b001_100_001);
+}
+
+#[test]
+fn test_no_rectangle_in_single_square() {
+    let mut notes: Notes = [[CandidateSet::EMPTY; GRID_SIZE]; GRID_SIZE];
+    for (row, col) in [(0, 0), (0, 1), (1, 0)] {
+        notes[row][col] = CandidateSet::from_bits(0b000_000_011);
+    }
+    notes[1][1] = CandidateSet::from_bits(0b000_010_011);
+    assert!(find_unique_rectangles(&Layout::default(), &notes).is_empty());
+}
+
+#[test]
+fn test_no_rectangle_across_extra_house() {
+    let mut notes = notes_with_floor();
+    notes[1][0] = CandidateSet::from_bits(0b000_000_011);
+    notes[1][3] = CandidateSet::from_bits(0b000_010_011);
+    assert!(find_unique_rectangles(&Layout::default().with_diagonals(), &notes).is_empty());
+    assert!(find_unique_rectangles(&Layout::default().with_windows(), &notes).is_empty());
+}
+
+#[test]
+fn test_bug_plus_one() {
+    let mut notes = bug_notes();
+    assert_eq!(find_bug_plus_one(&Layout::default(), &notes), None);
+    notes[0][0].insert(1);
+    let bug = Uniqueness {
+        kind: UniquenessKind::BugPlusOne,
+        cells: vec![(0, 0)],
+        eliminations: vec![((0, 0), 5), ((0, 0), 6)],
+    };
+    assert_eq!(find_bug_plus_one(&Layout::default(), &notes), Some(bug));
+    assert!(!use_uniqueness(&Layout::default(), &mut notes).is_empty());
+    assert_eq!(notes[0][0].bits(), 0b000_000_001);
+}
+
+#[test]
+fn test_no_bug_with_two_extra_cells() {
+    let mut notes = bug_notes();
+    notes[0][0].insert(1);
+    notes[8][8].insert(5);
+    assert_eq!(find_bug_plus_one(&Layout::default(), &notes), None);
+}
@@ -0,0 +1,400 @@
+use std::fmt;
+use crate::common::puzzle::Puzzle;
+use crate::solve::notes::Notes;
+use crate::solve::candidate_set::CandidateSet;
+use crate::solve::coordinates::*;
+use crate::solve::map_notes::House;
+use crate::solve::cages::Cage;
+use crate::solve::lines::Line;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolverError {
+    OutOfRange { cell: Point, value: u8 },
+    DuplicateValue { value: u8, cells: [Point; 2] },
+    EmptyCandidates { cell: Point },
+    MissingDigit { value: u8, house: House, index: usize },
+    InvalidRegion { index: usize },
+    BrokenCage { index: usize },
+    BrokenLine { index: usize },
+    ConsecutiveValues { cells: [Point; 2] },
+    NoSolution,
+    CapTooSmall { cap: usize },
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolverError::OutOfRange { cell, value } => {
+                write!(f, "Value {} at {} is out of range", value, display_point(*cell))
+            }
+            SolverError::DuplicateValue { value, cells } => {
+                let (first, second) = (display_point(cells[0]), display_point(cells[1]));
+                write!(f, "Value {} appears twice, at {} and {}", value, first, second)
+            }
+            SolverError::EmptyCandidates { cell } => {
+                write!(f, "No value fits at {}", display_point(*cell))
+            }
+            SolverError::MissingDigit { value, house, index } => {
+                write!(f, "Value {} can't be placed in {:?} {}", value, house, index + 1)
+            }
+            SolverError::InvalidRegion { index } => {
+                write!(f, "Region {} has the wrong number of cells", index + 1)
+            }
+            SolverError::BrokenCage { index } => {
+                write!(f, "The values in cage {} can't add up to its sum", index + 1)
+            }
+            SolverError::BrokenLine { index } => {
+                write!(f, "The values on line {} break its rule", index + 1)
+            }
+            SolverError::ConsecutiveValues { cells } => {
+                let (first, second) = (display_point(cells[0]), display_point(cells[1]));
+                write!(f, "Values at {} and {} are consecutive", first, second)
+            }
+            SolverError::NoSolution => write!(f, "The puzzle has no solution"),
+            SolverError::CapTooSmall { cap } => {
+                write!(f, "A cap of {} solutions can't show that a solution is unique", cap)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolverError {}
+
+fn display_point((row, col): Point) -> String {
+    format!("row {}, col {}", row + 1, col + 1)
+}
+
+pub fn validate_puzzle<const N: usize>(
+    layout: &Layout<N>,
+    puzzle: &Puzzle<N>
+) -> Result<(), SolverError> {
+    for (row, puzzle_row) in puzzle.iter().enumerate() {
+        for (col, value) in puzzle_row.iter().enumerate() {
+            if *value as usize > N {
+                return Err(SolverError::OutOfRange { cell: (row, col), value: *value });
+            }
+        }
+    }
+    for index in 0..N {
+        for (_, coordinates) in layout.get_houses(index) {
+            check_duplicates(puzzle, &coordinates)?;
+        }
+    }
+    for coordinates in layout.get_extra_houses() {
+        check_duplicates(puzzle, coordinates)?;
+    }
+    check_relations(layout, puzzle)?;
+    check_cages::<N>(layout.get_cages())?;
+    check_lines::<N>(layout.get_lines())?;
+    let notes = [[CandidateSet::full(N); N]; N];
+    for (index, cage) in layout.get_cages().iter().enumerate() {
+        check_duplicates(puzzle, &cage.cells)?;
+        if !cage.fits(puzzle, &notes) {
+            return Err(SolverError::BrokenCage { index });
+        }
+    }
+    for (index, line) in layout.get_lines().iter().enumerate() {
+        if !line.fits(layout, puzzle, &notes) {
+            return Err(SolverError::BrokenLine { index });
+        }
+    }
+    Ok(())
+}
+
+pub fn check_cages<const N: usize>(cages: &[Cage]) -> Result<(), SolverError> {
+    let mut covered = [[false; N]; N];
+    for (index, cage) in cages.iter().enumerate() {
+        for (row, col) in cage.cells.iter() {
+            match covered.get_mut(*row).and_then(|covered_row| covered_row.get_mut(*col)) {
+                Some(cell) if !*cell => *cell = true,
+                _ => return Err(SolverError::BrokenCage { index }),
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn check_lines<const N: usize>(lines: &[Line]) -> Result<(), SolverError> {
+    for (index, line) in lines.iter().enumerate() {
+        let mut covered = [[false; N]; N];
+        for (row, col) in line.cells.iter() {
+            match covered.get_mut(*row).and_then(|covered_row| covered_row.get_mut(*col)) {
+                Some(cell) if !*cell => *cell = true,
+                _ => return Err(SolverError::BrokenLine { index }),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_duplicates<const N: usize>(
+    puzzle: &Puzzle<N>,
+    coordinates: &[Point]
+) -> Result<(), SolverError> {
+    let mut seen: [Option<Point>; N] = [None; N];
+    for (row, col) in coordinates.iter() {
+        let value = puzzle[*row][*col];
+        if value == 0 {
+            continue;
+        }
+        if let Some(first) = seen[(value - 1) as usize] {
+            return Err(SolverError::DuplicateValue { value, cells: [first, (*row, *col)] });
+        }
+        seen[(value - 1) as usize] = Some((*row, *col));
+    }
+    Ok(())
+}
+
+fn check_relations<const N: usize>(
+    layout: &Layout<N>,
+    puzzle: &Puzzle<N>
+) -> Result<(), SolverError> {
+    for (row, puzzle_row) in puzzle.iter().enumerate() {
+        for (col, value) in puzzle_row.iter().enumerate() {
+            if *value == 0 {
+                continue;
+            }
+            for (other_row, other_col) in layout.get_moves((row, col)) {
+                if puzzle[other_row][other_col] == *value {
+                    let cells = [(row, col), (other_row, other_col)];
+                    return Err(SolverError::DuplicateValue { value: *value, cells });
+                }
+            }
+            for (other_row, other_col) in layout.get_neighbours((row, col)) {
+                let other = puzzle[other_row][other_col];
+                if other != 0 && other.abs_diff(*value) == 1 {
+                    let cells = [(row, col), (other_row, other_col)];
+                    return Err(SolverError::ConsecutiveValues { cells });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_notes<const N: usize>(
+    layout: &Layout<N>,
+    puzzle: &Puzzle<N>,
+    notes: &Notes<N>
+) -> Result<(), SolverError> {
+    for (row, puzzle_row) in puzzle.iter().enumerate() {
+        for (col, value) in puzzle_row.iter().enumerate() {
+            if *value == 0 && notes[row][col].is_empty() {
+                return Err(SolverError::EmptyCandidates { cell: (row, col) });
+            }
+        }
+    }
+    for index in 0..N {
+        for (house, coordinates) in layout.get_houses(index) {
+            check_missing(puzzle, notes, &coordinates, house, index)?;
+        }
+    }
+    for (index, coordinates) in layout.get_extra_houses().iter().enumerate() {
+        check_missing(puzzle, notes, coordinates, House::Extra, index)?;
+    }
+    Ok(())
+}
+
+fn check_missing<const N: usize>(
+    puzzle: &Puzzle<N>,
+    notes: &Notes<N>,
+    coordinates: &Coordinates<N>,
+    house: House,
+    index: usize
+) -> Result<(), SolverError> {
+    for value in 1..=N as u8 {
+        let is_possible = coordinates.iter().any(|(row, col)| {
+            puzzle[*row][*col] == value || notes[*row][*col].contains(value as usize)
+        });
+        if !is_possible {
+            return Err(SolverError::MissingDigit { value, house, index });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::grid_size::GRID_SIZE;
+    use crate::solve::lines::*;
+    use crate::solve::notes_manager::NotesManager;
+
+    const PUZZLE: Puzzle = [
+        [8, 0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 3, 6, 0, 0, 0, 0, 0],
+        [0, 7, 0, 0, 9, 0, 2, 0, 0],
+        [0, 5, 0, 0, 0, 7, 0, 0, 0],
+        [0, 0, 0, 0, 4, 5, 7, 0, 0],
+        [0, 0, 0, 1, 0, 0, 0, 3, 0],
+        [0, 0, 1, 0, 0, 0, 0, 6, 8],
+        [0, 0, 8, 5, 0, 0, 0, 1, 0],
+        [0, 9, 0, 0, 0, 0, 4, 0, 0],
+    ];
+
+    fn fill(puzzle: Puzzle) -> Result<Notes, SolverError> {
+        let mut notes_manager = NotesManager::new(puzzle);
+        notes_manager.fill()?;
+        Ok(notes_manager.get())
+    }
+
+    #[test]
+    fn test_valid_puzzle() {
+        assert_eq!(validate_puzzle(&Layout::default(), &PUZZLE), Ok(()));
+        assert!(fill(PUZZLE).is_ok());
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let mut puzzle = PUZZLE;
+        puzzle[3][4] = 10;
+        let expected = SolverError::OutOfRange { cell: (3, 4), value: 10 };
+        assert_eq!(validate_puzzle(&Layout::default(), &puzzle), Err(expected.clone()));
+        assert_eq!(fill(puzzle), Err(expected));
+    }
+
+    #[test]
+    fn test_duplicate_given() {
+        let mut puzzle = PUZZLE;
+        puzzle[0][5] = 8;
+        let expected = SolverError::DuplicateValue { value: 8, cells: [(0, 0), (0, 5)] };
+        assert_eq!(validate_puzzle(&Layout::default(), &puzzle), Err(expected.clone()));
+        assert_eq!(fill(puzzle), Err(expected));
+    }
+
+    #[test]
+    fn test_extra_houses() {
+        let mut puzzle = PUZZLE;
+        puzzle[2][2] = 4;
+        let layout = Layout::default().with_diagonals();
+        let expected = SolverError::DuplicateValue { value: 4, cells: [(2, 2), (4, 4)] };
+        assert_eq!(validate_puzzle(&Layout::default(), &puzzle), Ok(()));
+        assert_eq!(validate_puzzle(&layout, &puzzle), Err(expected));
+        let mut notes = fill(PUZZLE).unwrap();
+        for (i, row) in notes.iter_mut().enumerate() {
+            row[i].remove(9);
+        }
+        let expected = SolverError::MissingDigit { value: 9, house: House::Extra, index: 0 };
+        assert_eq!(validate_notes(&layout, &PUZZLE, &notes), Err(expected));
+    }
+
+    #[test]
+    fn test_broken_cages() {
+        let with_cage = |cells: &[Point], sum| {
+            Layout::default().with_cages(vec![Cage { cells: cells.to_vec(), sum }]).unwrap()
+        };
+        let layout = with_cage(&[(0, 0), (0, 1)], 17);
+        assert_eq!(validate_puzzle(&layout, &PUZZLE), Ok(()));
+        let layout = with_cage(&[(1, 2), (1, 3)], 10);
+        assert_eq!(validate_puzzle(&layout, &PUZZLE), Err(SolverError::BrokenCage { index: 0 }));
+        let layout = with_cage(&[(0, 0), (1, 0), (1, 1)], 9);
+        assert_eq!(validate_puzzle(&layout, &PUZZLE), Err(SolverError::BrokenCage { index: 0 }));
+        let layout = with_cage(&[(0, 0), (4, 4)], 12);
+        let expected = SolverError::DuplicateValue { value: 4, cells: [(0, 0), (4, 4)] };
+        let mut puzzle = PUZZLE;
+        puzzle[0][0] = 4;
+        assert_eq!(validate_puzzle(&layout, &puzzle), Err(expected));
+    }
+
+    #[test]
+    fn test_out_of_range_cage() {
+        let cage = Cage { cells: vec![(8, 8), (9, 0)], sum: 10 };
+        let layout = Layout::<GRID_SIZE>::default().with_cages(vec![cage]);
+        assert_eq!(layout.err(), Some(SolverError::BrokenCage { index: 0 }));
+        let cage = Cage { cells: vec![(0, 9)], sum: 5 };
+        assert_eq!(check_cages::<GRID_SIZE>(&[cage]), Err(SolverError::BrokenCage { index: 0 }));
+    }
+
+    #[test]
+    fn test_overlapping_cages() {
+        let first = Cage { cells: vec![(0, 0), (0, 1)], sum: 3 };
+        let second = Cage { cells: vec![(0, 1), (0, 2)], sum: 11 };
+        let layout = Layout::<GRID_SIZE>::default().with_cages(vec![first.clone()]).unwrap();
+        let error = SolverError::BrokenCage { index: 1 };
+        assert_eq!(layout.with_cages(vec![second.clone()]).err(), Some(error.clone()));
+        assert_eq!(check_cages::<GRID_SIZE>(&[first, second]), Err(error));
+        let repeated = Cage { cells: vec![(4, 4), (4, 4)], sum: 10 };
+        let error = SolverError::BrokenCage { index: 0 };
+        assert_eq!(check_cages::<GRID_SIZE>(&[repeated]), Err(error));
+    }
+
+    #[test]
+    fn test_broken_lines() {
+        let thermometer = Line::new(LineKind::Thermometer, vec![(1, 3), (1, 4), (1, 5)]);
+        let layout = Layout::default().with_lines(vec![thermometer]).unwrap();
+        assert_eq!(validate_puzzle(&layout, &PUZZLE), Ok(()));
+        let thermometer = Line::new(LineKind::Thermometer, vec![(1, 3), (1, 2)]);
+        let layout = Layout::default().with_lines(vec![thermometer]).unwrap();
+        assert_eq!(validate_puzzle(&layout, &PUZZLE), Err(SolverError::BrokenLine { index: 0 }));
+        let arrow = Line::new(LineKind::Arrow, vec![(0, 0), (0, 1), (1, 2)]);
+        let whispers = Line::new(LineKind::Whispers, vec![(4, 4), (4, 5)]);
+        let layout = Layout::default().with_lines(vec![arrow, whispers]).unwrap();
+        assert_eq!(validate_puzzle(&layout, &PUZZLE), Err(SolverError::BrokenLine { index: 1 }));
+    }
+
+    #[test]
+    fn test_invalid_line_cells() {
+        let arrow = Line::new(LineKind::Arrow, vec![(8, 7), (8, 8), (8, 9)]);
+        let layout = Layout::<GRID_SIZE>::default().with_lines(vec![arrow]);
+        assert_eq!(layout.err(), Some(SolverError::BrokenLine { index: 0 }));
+        let whispers = Line::new(LineKind::Whispers, vec![(0, 0), (0, 1)]);
+        let thermometer = Line::new(LineKind::Thermometer, vec![(1, 1), (1, 2), (1, 1)]);
+        let error = SolverError::BrokenLine { index: 1 };
+        assert_eq!(check_lines::<GRID_SIZE>(&[whispers, thermometer]), Err(error));
+        let palindrome = Line::new(LineKind::Palindrome, vec![(0, 1), (0, 0)]);
+        let whispers = Line::new(LineKind::Whispers, vec![(0, 0), (0, 1)]);
+        assert_eq!(check_lines::<GRID_SIZE>(&[palindrome, whispers]), Ok(()));
+    }
+
+    #[test]
+    fn test_chess_constraints() {
+        let mut puzzle = PUZZLE;
+        puzzle[3][4] = 2;
+        let expected = SolverError::DuplicateValue { value: 2, cells: [(2, 6), (3, 4)] };
+        assert_eq!(validate_puzzle(&Layout::default(), &puzzle), Ok(()));
+        assert_eq!(validate_puzzle(&Layout::default().with_anti_knight(), &puzzle), Err(expected));
+        let mut puzzle = PUZZLE;
+        puzzle[3][7] = 2;
+        let expected = SolverError::DuplicateValue { value: 2, cells: [(2, 6), (3, 7)] };
+        assert_eq!(validate_puzzle(&Layout::default(), &puzzle), Ok(()));
+        assert_eq!(validate_puzzle(&Layout::default().with_anti_king(), &puzzle), Err(expected));
+        let layout = Layout::default().with_non_consecutive();
+        let expected = SolverError::ConsecutiveValues { cells: [(4, 4), (4, 5)] };
+        assert_eq!(validate_puzzle(&layout, &PUZZLE), Err(expected));
+    }
+
+    #[test]
+    fn test_empty_candidates() {
+        let mut puzzle = PUZZLE;
+        puzzle[0][6] = 3;
+        puzzle[1][6] = 9;
+        assert_eq!(validate_puzzle(&Layout::default(), &puzzle), Ok(()));
+        assert_eq!(fill(puzzle), Err(SolverError::EmptyCandidates { cell: (7, 6) }));
+    }
+
+    #[test]
+    fn test_missing_digit() {
+        let mut notes = fill(PUZZLE).unwrap();
+        for note in notes[0].iter_mut() {
+            note.remove(9);
+        }
+        let expected = SolverError::MissingDigit { value: 9, house: House::Row, index: 0 };
+        assert_eq!(validate_notes(&Layout::default(), &PUZZLE, &notes), Err(expected));
+    }
+
+    #[test]
+    fn test_display() {
+        let error = SolverError::DuplicateValue { value: 8, cells: [(0, 0), (0, 5)] };
+        assert_eq!(error.to_string(), "Value 8 appears twice, at row 1, col 1 and row 1, col 6");
+        let error = SolverError::MissingDigit { value: 9, house: House::Region, index: 4 };
+        assert_eq!(error.to_string(), "Value 9 can't be placed in Region 5");
+        let error = SolverError::InvalidRegion { index: 2 };
+        assert_eq!(error.to_string(), "Region 3 has the wrong number of cells");
+        let error = SolverError::BrokenCage { index: 0 };
+        assert_eq!(error.to_string(), "The values in cage 1 can't add up to its sum");
+        let error = SolverError::BrokenLine { index: 2 };
+        assert_eq!(error.to_string(), "The values on line 3 break its rule");
+        let error = SolverError::ConsecutiveValues { cells: [(4, 4), (4, 5)] };
+        assert_eq!(error.to_string(), "Values at row 5, col 5 and row 5, col 6 are consecutive");
+    }
+}
@@ -0,0 +1,175 @@
+use crate::solve::notes::*;
+use crate::solve::candidate_set::CandidateSet;
+use crate::solve::coordinates::*;
+use crate::solve::deduction::Deduction;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WingKind {
+    Xy,
+    Xyz,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Wing {
+    pub kind: WingKind,
+    pub pivot: Point,
+    pub pincers: [Point; 2],
+    pub value: usize,
+    pub eliminations: Vec<Point>,
+}
+
+impl WingKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WingKind::Xy => "XY-Wing",
+            WingKind::Xyz => "XYZ-Wing",
+        }
+    }
+}
+
+impl Wing {
+    pub fn clear<const N: usize>(&self, notes: &mut Notes<N>) -> bool {
+        clear_value(notes, self.value, &self.eliminations)
+    }
+
+    pub fn apply<const N: usize>(&self, notes: &mut Notes<N>) -> Option<Deduction> {
+        let reasons = [self.pivot, self.pincers[0], self.pincers[1]];
+        Deduction::record(self.kind.name(), &reasons, notes, |notes| self.clear(notes))
+    }
+}
+
+pub fn use_wings<const N: usize>(layout: &Layout<N>, notes: &mut Notes<N>) -> Vec<Deduction> {
+    find_wings(layout, notes).iter().filter_map(|wing| wing.apply(notes)).collect()
+}
+
+pub fn find_wings<const N: usize>(layout: &Layout<N>, notes: &Notes<N>) -> Vec<Wing> {
+    let mut found = Vec::new();
+    for row in 0..N {
+        for col in 0..N {
+            let kind = match notes[row][col].count() {
+                2 => WingKind::Xy,
+                3 => WingKind::Xyz,
+                _ => {
+                    continue;
+                }
+            };
+            found.extend(find_wings_on_pivot(layout, notes, (row, col), kind));
+        }
+    }
+    found
+}
+
+fn find_wings_on_pivot<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    pivot: Point,
+    kind: WingKind
+) -> Vec<Wing> {
+    let pivot_note = notes[pivot.0][pivot.1];
+    let pincers: Vec<Point> = layout.get_peers(pivot)
+        .into_iter()
+        .filter(|(row, col)| notes[*row][*col].count() == 2)
+        .collect();
+    let mut found = Vec::new();
+    for (index, first) in pincers.iter().enumerate() {
+        for second in pincers[index + 1..].iter() {
+            let first_note = notes[first.0][first.1];
+            let second_note = notes[second.0][second.1];
+            let Some(value) = (first_note & second_note).get_single() else {
+                continue;
+            };
+            if !is_wing(kind, pivot_note, first_note, second_note) {
+                continue;
+            }
+            let mut visible = vec![*first, *second];
+            if kind == WingKind::Xyz {
+                visible.push(pivot);
+            }
+            let eliminations = get_eliminations(layout, notes, value, &visible);
+            if !eliminations.is_empty() {
+                found.push(Wing { kind, pivot, pincers: [*first, *second], value, eliminations });
+            }
+        }
+    }
+    found
+}
+
+fn is_wing(
+    kind: WingKind,
+    pivot: CandidateSet,
+    first: CandidateSet,
+    second: CandidateSet
+) -> bool {
+    match kind {
+        WingKind::Xy => {
+            (pivot | first | second).count() == 3 && (first & second & pivot).is_empty()
+        }
+        WingKind::Xyz => (first | second) == pivot,
+    }
+}
+
+fn get_eliminations<const N: usize>(
+    layout: &Layout<N>,
+    notes: &Notes<N>,
+    value: usize,
+    visible: &[Point]
+) -> Vec<Point> {
+    layout.get_peers(visible[0])
+        .into_iter()
+        .filter(|(row, col)| notes[*row][*col].contains(value))
+        .filter(|point| visible.iter().all(|cell| layout.sees(*cell, *point)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::grid_size::GRID_SIZE;
+
+    #[test]
+    fn test_xy_wing() {
+        let layout = Layout::default();
+        let mut notes: Notes = [[CandidateSet::EMPTY; GRID_SIZE]; GRID_SIZE];
+        notes[0][0] = CandidateSet::from_bits(0b000_000_011);
+        notes[0][5] = CandidateSet::from_bits(0b000_000_101);
+        notes[4][0] = CandidateSet::from_bits(0b000_000_110);
+        notes[4][5] = CandidateSet::from_bits(0b000_001_100);
+        notes[0][4] = CandidateSet::from_bits(0b000_001_100);
+        let wings = find_wings(&layout, &notes);
+        assert_eq!(wings.len(), 1);
+        assert_eq!(wings[0].kind, WingKind::Xy);
+        assert_eq!(wings[0].pivot, (0, 0));
+        assert_eq!(wings[0].pincers, [(0, 5), (4, 0)]);
+        assert_eq!(wings[0].value, 3);
+        assert_eq!(wings[0].eliminations, vec![(4, 5)]);
+
+        let deductions = use_wings(&layout, &mut notes);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].technique, "XY-Wing");
+        assert_eq!(deductions[0].reasons, vec![(0, 0), (0, 5), (4, 0)]);
+        assert_eq!(deductions[0].eliminations, vec![((4, 5), 3)]);
+        assert_eq!(notes[4][5].bits(), 0b000_001_000);
+        assert_eq!(notes[0][4].bits(), 0b000_001_100);
+        assert!(use_wings(&layout, &mut notes).is_empty());
+    }
+
+    #[test]
+    fn test_xyz_wing() {
+        let layout = Layout::default();
+        let mut notes: Notes = [[CandidateSet::EMPTY; GRID_SIZE]; GRID_SIZE];
+        notes[1][1] = CandidateSet::from_bits(0b000_000_111);
+        notes[1][7] = CandidateSet::from_bits(0b000_000_101);
+        notes[2][2] = CandidateSet::from_bits(0b000_000_110);
+        notes[1][2] = CandidateSet::from_bits(0b000_100_100);
+        notes[2][7] = CandidateSet::from_bits(0b000_000_100);
+        let wings = find_wings(&layout, &notes);
+        assert_eq!(wings.len(), 1);
+        assert_eq!(wings[0].kind, WingKind::Xyz);
+        assert_eq!(wings[0].pivot, (1, 1));
+        assert_eq!(wings[0].value, 3);
+        assert_eq!(wings[0].eliminations, vec![(1, 2)]);
+        assert!(!use_wings(&layout, &mut notes).is_empty());
+        assert_eq!(notes[1][2].bits(), 0b000_100_000);
+        assert_eq!(notes[2][7].bits(), 0b000_000_100);
+    }
+}